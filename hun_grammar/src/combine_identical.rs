@@ -4,19 +4,42 @@ peg::parser!{
     pub grammar combine_identical() for str
     {
         //* Perform geminate simplification next to consonants
-        pub rule input() -> String =
-            g: grapheme() i: input() { format!( "{}{}", &g, &i ) } /
-            grapheme()
+        pub rule input() -> Vec<(usize, usize, String)> =
+            chunks: ( across_boundary() /
+                      p: position!() g: grapheme() q: position!()
+                      { vec![(p, q, g)] } )+
+        { chunks.concat() }
 
         rule grapheme() -> String =
             no_geminate() /
-            geminate_sequence()
+            geminate_sequence() /
+            boundary()
+
+        // Identical segments across a morpheme, compound or word
+        // boundary are the two halves of a geminate, the boundary
+        // staying between them (kéz+zel, kis|szék), each with the
+        // span of its letters.
+        rule across_boundary() -> Vec<(usize, usize, String)> =
+            p: position!() g1: geminate_sequence() q: position!()
+            b: boundary() r: position!()
+            g2: geminate_sequence() t: position!()
+        {?
+            if g1.to_uppercase() == g2.to_uppercase()
+            {
+                let half = g1.to_lowercase();
+                Ok( vec![(p, q, half.clone()), (q, r, b), (r, t, half)] )
+            }
+            else { Err( "identical segments" ) }
+        }
+
+        rule boundary() -> String =
+            b: $( "+" / "|" / "#" ) { b.to_string() }
 
         rule no_geminate() -> String =
-            s: $("a" / "e" / "i" / "o" / "u" /
+            s: $("a" / "e" / "ë" / "i" / "o" / "u" /
                  "á" / "é" / "í" / "ó" / "ú" /
                  "ö" / "ő" / "ü" / "ű" /
-                 "q" / "w" / "x" / "y")
+                 "q" / "w" / "x" / "y" / "ɣ" / "ɦ")
             { s.to_string() }
         
         rule geminate_sequence() -> String =
@@ -70,7 +93,11 @@ peg::parser!{
             "Kk" { "K".to_string() } /
             "k" { "k".to_string() } /
             "K" { "K".to_string() } /
+            "ll" { "L".to_string() } /
+            "lL" { "L".to_string() } /
+            "Ll" { "L".to_string() } /
             "l" { "l".to_string() } /
+            "L" { "L".to_string() } /
             "mm" { "M".to_string() } /
             "mM" { "M".to_string() } /
             "Mm" { "M".to_string() } /
@@ -121,7 +148,11 @@ peg::parser!{
             "Vv" { "V".to_string() } /
             "v" { "v".to_string() } /
             "V" { "V".to_string() } /
+            "zz" { "Z".to_string() } /
+            "zZ" { "Z".to_string() } /
+            "Zz" { "Z".to_string() } /
             "z" { "z".to_string() } /
+            "Z" { "Z".to_string() } /
             "žž" { "Ž".to_string() } /
             "žŽ" { "Ž".to_string() } /
             "Žž" { "Ž".to_string() } /
@@ -129,3 +160,30 @@ peg::parser!{
             "Ž" { "Ž".to_string() }
     }
 }
+
+#[cfg(test)]
+mod tests
+{
+    use super::combine_identical;
+
+    fn combine( s: &str ) -> String
+    {
+        combine_identical::input( s ).unwrap().into_iter().
+            map( | (_, _, g) | g ).collect()
+    }
+
+    #[test]
+    fn identical_letters_make_geminates()
+    {
+        assert_eq!( combine( "kassa" ), "kaSa" );
+        assert_eq!( combine( "itt" ), "iT" );
+    }
+
+    #[test]
+    fn boundary_stays_between_the_halves()
+    {
+        assert_eq!( combine( "kéz+zel" ), "kéz+zel" );
+        assert_eq!( combine( "kis|sék" ), "kis|sék" );
+        assert_eq!( combine( "ďď#ďerek" ), "ď#ďerek" );
+    }
+}
@@ -1,48 +1,193 @@
 extern crate peg;
+use std::ops::Range;
+
 use crate::simple_grapheme;
 use crate::obstruent_voice;
 use crate::orthographic_combinations;
 use crate::combine_identical;
 use crate::simplify_geminates;
+use crate::h_realization;
+use crate::l_assimilation;
+use crate::j_assimilation;
+use crate::degemination;
+use crate::l_deletion;
+use crate::speech_style::OptionalRule;
+use crate::dialect::Dialect;
+
+/// An intermediate representation: each character comes with
+/// the byte span of the original input it has been derived from.
+pub type Spanned = Vec<(char, Range<usize>)>;
+
+/// A string whose characters are their own sources.
+pub fn spanned( s: &str ) -> Spanned
+{
+    s.char_indices().map( | (i, c) | ( c, i..i + c.len_utf8() ) ).collect()
+}
+
+/// The characters of a spanned string.
+pub fn text( s: &[(char, Range<usize>)] ) -> String
+{
+    s.iter().map( | (c, _) | *c ).collect()
+}
+
+/// Run a rewriting stage returning converted chunks with their
+/// positions; each chunk inherits the span covering the input
+/// characters it has been converted from.
+pub fn apply<E: ToString>(
+    input: &[(char, Range<usize>)],
+    stage: impl Fn( &str ) -> Result<Vec<(usize, usize, String)>, E> )
+    -> Result<Vec<(String, Range<usize>)>, String>
+{
+    let s = text( input );
+    let offsets: Vec<usize> = s.char_indices().map( | (i, _) | i ).collect();
+    let mut result = Vec::new();
+    for ( p, q, out ) in stage( &s ).map_err( | e | e.to_string() )?
+    {
+        let first = offsets.binary_search( &p ).unwrap_or_else( | i | i );
+        let last = offsets.binary_search( &q ).unwrap_or_else( | i | i );
+        let span =
+            if first < last
+            {
+                input[first].1.start..input[last - 1].1.end
+            }
+            else
+            {
+                match input.get( first )
+                {
+                    Some( (_, span) ) => span.start..span.start,
+                    None => match input.last()
+                    {
+                        Some( (_, span) ) => span.end..span.end,
+                        None => 0..0
+                    }
+                }
+            };
+        result.push( ( out, span ) );
+    }
+    Ok( result )
+}
+
+/// Chunks split into characters, each inheriting the span of its chunk.
+pub fn characters( chunks: Vec<(String, Range<usize>)> ) -> Spanned
+{
+    let mut result = Vec::new();
+    for ( out, span ) in chunks
+    {
+        for c in out.chars()
+        {
+            result.push( ( c, span.clone() ) );
+        }
+    }
+    result
+}
 
-// Convert to single-grapheme sequences;
+// Convert to single-grapheme sequences (the digraph "ch" to `ch`,
+// "ch" and "ph" being read letter by letter if it is `None`);
 // apply obstruent voice assimilation;
 // convert orthographical combinations into geminates
-// simplify geminate consonants next to consonants
+// apply the optional rules in `rules` (l- and j-assimilation before,
+// degemination after combining identical segments), and the
+// l-deletion of `dialect` before combining identical segments;
+// realize /h/ depending on its position (`keep_final_h` blocks
+// word-final deletion for lexical exceptions, it has an element
+// for each word of a phrase, words being separated by `#`);
+// simplify geminate consonants next to consonants;
+// replace segments by their realization in `dialect`.
+// Each segment of the result comes with the span of `s` it has
+// been derived from.
 
-pub fn convert_graphemes( s: &'static str )
-    -> Result<Vec<String>, String>
+pub fn convert_graphemes(
+    s: &[(char, Range<usize>)], keep_final_h: &[bool],
+    rules: &[OptionalRule], ch: Option<&str>, dialect: Dialect )
+    -> Result<Vec<(String, Range<usize>)>, String>
 {
-    match simple_grapheme::simple_grapheme::input( s )
+    let graphemic =
+        match apply( s, | s | simple_grapheme::simple_grapheme::input( s, ch ) )
+        {
+            Ok( graphemic ) => characters( graphemic ),
+            Err( e ) => { return Err( format!( "simple_grapheme: {}", e ) ); }
+        };
+    let mut combined =
+        characters(
+            apply( &graphemic,
+                   orthographic_combinations::orthographic_combinations::input )? );
+
+    let mut modified = true;
+    while modified
     {
-        Ok( graphemic ) =>
+        let new_assimilated =
+            characters(
+                apply( &combined, obstruent_voice::obstruent_voice::input )? );
+        if text( &new_assimilated ) == text( &combined )
         {
-            let mut combined =
-                orthographic_combinations::orthographic_combinations::input(
-                    &graphemic ).unwrap();
+            modified = false;
+        }
+        else { combined = new_assimilated; }
+    }
 
-            let mut modified = true;
-            while modified
-            {
-                let new_assimilated =
-                    obstruent_voice::obstruent_voice::input( &combined ).
-                    unwrap();
-                if new_assimilated == combined
-                {
-                    modified = false;
-                }
-                else { combined = new_assimilated; }
-            }
+    if rules.contains( &OptionalRule::LAssimilation )
+    {
+        combined =
+            characters(
+                apply( &combined, l_assimilation::l_assimilation::input )? );
+    }
+    if rules.contains( &OptionalRule::JAssimilation )
+    {
+        combined =
+            characters(
+                apply( &combined, j_assimilation::j_assimilation::input )? );
+    }
+
+    if dialect.l_deletion()
+    {
+        combined =
+            characters(
+                apply( &combined, l_deletion::l_deletion::input )? );
+    }
+
+    combined =
+        characters(
+            apply( &combined, combine_identical::combine_identical::input )? );
 
-            combined =
-                combine_identical::combine_identical::input( &combined ).
-                unwrap();
+    if rules.contains( &OptionalRule::Degemination )
+    {
+        combined =
+            characters(
+                apply( &combined, degemination::degemination::input )? );
+    }
 
-            let mut cons = false;
-            Ok(
-                simplify_geminates::simplify_geminates::input(
-                    &combined, &mut cons ).unwrap() )
+    let word_boundaries: Vec<Range<usize>> =
+        combined.iter().
+        filter( | (c, _) | *c == '#' ).
+        map( | (_, span) | span.clone() ).
+        collect();
+    let mut words: Vec<Spanned> = Vec::new();
+    for ( i, word ) in combined.split( | (c, _) | *c == '#' ).enumerate()
+    {
+        words.push(
+            characters(
+                apply( word,
+                       | s | h_realization::h_realization::input(
+                           s, keep_final_h[i] ) )? ) );
+    }
+    combined = Vec::new();
+    for ( i, word ) in words.into_iter().enumerate()
+    {
+        if i > 0
+        {
+            combined.push( ( '#', word_boundaries[i - 1].clone() ) );
         }
-        Err( e ) => Err( format!( "simple_grapheme: {}", e.to_string() ) )
+        combined.extend( word );
     }
+
+    let simplified =
+        apply( &combined,
+               | s |
+               {
+                   let mut cons = false;
+                   simplify_geminates::simplify_geminates::input( s, &mut cons )
+               } )?;
+    Ok( simplified.into_iter().
+        map( | (segment, span) | ( dialect.realize( &segment ).to_string(), span ) ).
+        collect() )
 }
@@ -0,0 +1,83 @@
+use crate::peg;
+
+peg::parser!{
+    pub grammar degemination() for str
+    {
+        //* Optional shortening of geminate consonants
+        //* in casual speech (itt -> it, kéz+zel -> ké+zel).
+        pub rule input() -> Vec<(usize, usize, String)> =
+            chunks: ( halves() /
+                      p: position!() g: grapheme() q: position!()
+                      { vec![(p, q, g)] } )+
+        { chunks.concat() }
+
+        rule grapheme() -> String =
+            geminate() / other()
+
+        // the halves of a geminate across a boundary, the second
+        // one being kept (kéz+zel -> ké+zel):
+        rule halves() -> Vec<(usize, usize, String)> =
+            h1: half() q: position!() b: $( "+" / "|" / "#" ) r: position!()
+            h2: half() t: position!()
+        {?
+            if h1 == h2
+            {
+                Ok( vec![(q, r, b.to_string()), (r, t, h2.to_string())] )
+            }
+            else { Err( "identical halves" ) }
+        }
+
+        rule half() -> &'input str =
+            $( "b" / "c" / "č" / "d" / "f" / "g" / "ď" / "h" / "j" / "k" /
+               "l" / "m" / "ń" / "n" / "p" / "r" / "s" / "š" / "ť" / "t" /
+               "v" / "ž" / "z" )
+
+        rule geminate() -> String =
+            "B" { "b".to_string() } /
+            "C" { "c".to_string() } /
+            "Č" { "č".to_string() } /
+            "D" { "d".to_string() } /
+            "F" { "f".to_string() } /
+            "G" { "g".to_string() } /
+            "Ď" { "ď".to_string() } /
+            "H" { "h".to_string() } /
+            "J" { "j".to_string() } /
+            "K" { "k".to_string() } /
+            "L" { "l".to_string() } /
+            "M" { "m".to_string() } /
+            "Ń" { "ń".to_string() } /
+            "N" { "n".to_string() } /
+            "P" { "p".to_string() } /
+            "R" { "r".to_string() } /
+            "S" { "s".to_string() } /
+            "Š" { "š".to_string() } /
+            "Ť" { "ť".to_string() } /
+            "T" { "t".to_string() } /
+            "V" { "v".to_string() } /
+            "Ž" { "ž".to_string() } /
+            "Z" { "z".to_string() }
+
+        rule other() -> String =
+            c: $[_] { c.to_string() }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::degemination;
+
+    fn degeminate( s: &str ) -> String
+    {
+        degemination::input( s ).unwrap().into_iter().
+            map( | (_, _, g) | g ).collect()
+    }
+
+    #[test]
+    fn geminates_are_shortened()
+    {
+        assert_eq!( degeminate( "iT" ), "it" );
+        assert_eq!( degeminate( "kéz+zel" ), "ké+zel" );
+        assert_eq!( degeminate( "kis|sék" ), "ki|sék" );
+    }
+}
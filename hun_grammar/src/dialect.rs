@@ -0,0 +1,43 @@
+/// A regional variety of Hungarian, selecting the segments
+/// and the processes of a conversion.  The input may contain
+/// the letter `ë` for closed e in every variety.
+#[derive( PartialEq, Eq, Clone, Copy, Debug )]
+pub enum Dialect
+{
+    /// The standard variety: `ë` is pronounced as open e.
+    Standard,
+    /// The varieties distinguishing closed ë from open e
+    /// (most dialects outside the capital: mëgy, fëlël).
+    ClosedE,
+    /// Palóc (northern): closed ë, and rounded long á [ɒː].
+    Paloc,
+    /// Western (Transdanubian): closed ë, closing diphthongs for
+    /// é, ó, ő (szép -> [sei̯p]), and l-deletion with compensatory
+    /// lengthening in preverbs and volt (el|megy -> é|megy, volt -> vót).
+    Western,
+}
+
+impl Dialect
+{
+    /// Whether a coda /l/ is deleted in the preverbs el, fel, föl
+    /// and in volt, lengthening the vowel before it.
+    pub fn l_deletion( &self ) -> bool
+    {
+        *self == Dialect::Western
+    }
+
+    /// The segment (in the notation of
+    /// `HungarianTable::from_grapheme`) realizing `segment`.
+    pub fn realize<'a>( &self, segment: &'a str ) -> &'a str
+    {
+        match ( self, segment )
+        {
+            ( Dialect::Standard, "ë" ) => "e",
+            ( Dialect::Paloc, "á" ) => "å",
+            ( Dialect::Western, "é" ) => "ei",
+            ( Dialect::Western, "ó" ) => "ou",
+            ( Dialect::Western, "ő" ) => "öü",
+            _ => segment
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use crate::tabular::table::{Table, Tier};
+
+// Segment by feature charts of an inventory, as read by phonology
+// tools and spreadsheets.  The plain chart has a column per tier
+// holding the elements of the tier (several for a contour, `nasal
+// slit`; a gap is an empty cell); the binary chart has a column
+// per autosegment, headed `tier=element`, with `+` for segments
+// having it on that tier and `-` for the others.  CSV fields are
+// quoted if they hold a comma, a quote or a line break; TSV fields
+// have their tabs, line breaks and backslashes escaped.
+
+/// The format of a chart.
+#[derive( PartialEq, Eq, Clone, Copy, Debug )]
+pub enum ChartFormat
+{
+    /// Comma-separated values.
+    Csv,
+    /// Tab-separated values.
+    Tsv,
+}
+
+impl ChartFormat
+{
+    fn separator( &self ) -> &'static str
+    {
+        match self
+        {
+            ChartFormat::Csv => ",",
+            ChartFormat::Tsv => "\t",
+        }
+    }
+
+    // A field of a row:
+    fn field( &self, field: &str ) -> String
+    {
+        match self
+        {
+            ChartFormat::Csv if field.contains( [',', '"', '\n', '\r'] ) =>
+                format!( "\"{}\"", field.replace( '"', "\"\"" ) ),
+            ChartFormat::Csv => field.to_string(),
+            ChartFormat::Tsv =>
+                field.replace( '\\', "\\\\" ).replace( '\t', "\\t" ).
+                replace( '\n', "\\n" ).replace( '\r', "\\r" ),
+        }
+    }
+
+    // A row of fields, ending in a line break:
+    fn row( &self, fields: &[String] ) -> String
+    {
+        let fields: Vec<String> = fields.iter().map( | f | self.field( f ) ).collect();
+        format!( "{}\n", fields.join( self.separator() ) )
+    }
+}
+
+// The elements of a tier, a slot spanning several columns
+// counted once:
+fn elements<'a>( tier: &Tier<'a> ) -> Vec<&'a str>
+{
+    tier.slots.iter().
+        filter( | slot | slot.is_right_aligned() ).
+        filter_map( | slot | slot.element() ).
+        collect()
+}
+
+/// The chart of `segments` (symbols and tables) with a column
+/// for each tier of `tier_names`.
+pub fn chart( segments: &[(&str, Table)], tier_names: &[&str], format: ChartFormat )
+    -> String
+{
+    let mut header = vec!["segment".to_string()];
+    header.extend( tier_names.iter().map( | name | name.to_string() ) );
+    let mut result = format.row( &header );
+    for ( symbol, table ) in segments
+    {
+        let mut row = vec![symbol.to_string()];
+        for i in 0..tier_names.len()
+        {
+            row.push( table.tiers.get( i ).
+                      map( | tier | elements( tier ).join( " " ) ).
+                      unwrap_or_default() );
+        }
+        result.push_str( &format.row( &row ) );
+    }
+    result
+}
+
+/// The binary chart of `segments` with a column for each
+/// autosegment (tier and element) of `autosegments`.
+pub fn binary_chart( segments: &[(&str, Table)], tier_names: &[&str],
+                     autosegments: &[(&str, &str)], format: ChartFormat )
+    -> String
+{
+    let mut header = vec!["segment".to_string()];
+    header.extend( autosegments.iter().
+                   map( | (tier, element) | format!( "{}={}", tier, element ) ) );
+    let mut result = format.row( &header );
+    for ( symbol, table ) in segments
+    {
+        let mut row = vec![symbol.to_string()];
+        for ( tier, element ) in autosegments
+        {
+            let present =
+                tier_names.iter().position( | name | name == tier ).
+                and_then( | i | table.tiers.get( i ) ).
+                is_some_and( | tier | elements( tier ).contains( element ) );
+            row.push( if present { "+" } else { "-" }.to_string() );
+        }
+        result.push_str( &format.row( &row ) );
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::{binary_chart, chart, ChartFormat};
+    use crate::tabular::table::Table;
+    use crate::tabular::tabular::Tabular;
+
+    const TIERS: [&str; 3] = ["voice", "airflow", "time"];
+
+    #[test]
+    fn contours_share_a_cell()
+    {
+        let segments = [
+            ( "c", Table::from_str( "2 voiceless, stop slit, 2 X;" ).unwrap() ),
+            ( "m", Table::from_str( "voiced, nasal, _;" ).unwrap() ),
+        ];
+        assert_eq!( chart( &segments, &TIERS, ChartFormat::Tsv ),
+                    "segment\tvoice\tairflow\ttime\n\
+                     c\tvoiceless\tstop slit\tX\n\
+                     m\tvoiced\tnasal\t\n" );
+        assert_eq!( binary_chart( &segments, &TIERS,
+                                  &[("voice", "voiced"), ("airflow", "stop"),
+                                    ("lips", "rounded")],
+                                  ChartFormat::Csv ),
+                    "segment,voice=voiced,airflow=stop,lips=rounded\n\
+                     c,-,+,-\n\
+                     m,+,-,-\n" );
+    }
+
+    #[test]
+    fn fields_are_quoted_or_escaped()
+    {
+        let table = Table::from_str( "voiced, _, _;" ).unwrap();
+        let segments = [( "d,z", table.clone() ), ( "\"", table.clone() ),
+                        ( "a\tb", table )];
+        assert_eq!( chart( &segments, &TIERS, ChartFormat::Csv ),
+                    "segment,voice,airflow,time\n\
+                     \"d,z\",voiced,,\n\
+                     \"\"\"\",voiced,,\n\
+                     a\tb,voiced,,\n" );
+        assert_eq!( chart( &segments, &TIERS, ChartFormat::Tsv ),
+                    "segment\tvoice\tairflow\ttime\n\
+                     d,z\tvoiced\t\t\n\
+                     \"\tvoiced\t\t\n\
+                     a\\tb\tvoiced\t\t\n" );
+    }
+}
@@ -0,0 +1,85 @@
+use crate::peg;
+
+peg::parser!{
+    pub grammar h_realization() for str
+    {
+        //* Position-sensitive realization of /h/:
+        //* word-final /h/ after a vowel is deleted (méh, düh),
+        //* unless `keep_final` is set (lexical exceptions: sah),
+        //* a compound boundary counts as the end of a word
+        //* (`#`: word boundary);
+        //* intervocalic /h/ is voiced (tehén, ahol, méh+ész);
+        //* a geminate /hh/ is neither deleted nor voiced, it is
+        //* a long voiceless [hː] (méhhez, méh|hús).
+        pub rule input( keep_final: bool ) -> Vec<(usize, usize, String)> =
+            ( p: position!() g: grapheme( keep_final ) q: position!()
+              { (p, q, g) } )+
+
+        rule grapheme( keep_final: bool ) -> String =
+            geminate_h() / final_h( keep_final ) / intervocalic_h() / other()
+
+        rule geminate_h() -> String =
+            v: vowel() g: $( "H" / "h" ( "+" / "|" ) "h" ) { format!( "{}{}", &v, g ) }
+
+        rule final_h( keep_final: bool ) -> String =
+            v: vowel() "h" &( "|" / "#" / ![_] )
+        {
+            if keep_final { format!( "{}h", &v ) }
+            else { v }
+        }
+
+        rule intervocalic_h() -> String =
+            v: vowel() "h" &( "+"? vowel() ) { format!( "{}ɦ", &v ) }
+
+        rule vowel() -> String =
+            s: $("a" / "e" / "ë" / "i" / "o" / "u" /
+                 "á" / "é" / "í" / "ó" / "ú" /
+                 "ö" / "ő" / "ü" / "ű")
+        { s.to_string() }
+
+        rule other() -> String =
+            c: $[_] { c.to_string() }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::h_realization;
+
+    fn realize( s: &str, keep_final: bool ) -> String
+    {
+        h_realization::input( s, keep_final ).unwrap().into_iter().
+            map( | (_, _, g) | g ).collect()
+    }
+
+    #[test]
+    fn final_h_is_deleted()
+    {
+        assert_eq!( realize( "méh", false ), "mé" );
+        assert_eq!( realize( "düh#van", false ), "dü#van" );
+        assert_eq!( realize( "méh|kas", false ), "mé|kas" );
+    }
+
+    #[test]
+    fn final_h_of_exceptions_is_kept()
+    {
+        assert_eq!( realize( "sah", true ), "sah" );
+    }
+
+    #[test]
+    fn intervocalic_h_is_voiced()
+    {
+        assert_eq!( realize( "tehén", false ), "teɦén" );
+        assert_eq!( realize( "ahol", false ), "aɦol" );
+        assert_eq!( realize( "méh+ész", false ), "méɦ+ész" );
+    }
+
+    #[test]
+    fn geminate_h_is_kept()
+    {
+        assert_eq!( realize( "méHez", false ), "méHez" );
+        assert_eq!( realize( "düH", false ), "düH" );
+        assert_eq!( realize( "méh|hús", false ), "méh|hús" );
+    }
+}
@@ -0,0 +1,188 @@
+use std::ops::Range;
+
+use crate::tabular::table::Table;
+
+use crate::ipa;
+
+// Vowel harmony of converted tables.  The vowels of the last
+// member of a word (after its last compound or word boundary)
+// are projected from the tongue, jaw and lips tiers (the last
+// part of a diphthong counts).  The front unrounded vowels above
+// low (i, í, é) are neutral; the others are harmonic, among them
+// the dialectal closed ë, which is mid (fëlhő, kávë).  A word
+// with back and front harmonic vowels is mixed (sofőr, nüansz),
+// a word with back and neutral vowels is back (papír), a word
+// with only neutral vowels front unrounded (víz); front words are
+// rounded if their last harmonic vowel is rounded (föld, tető,
+// but öreg).
+
+/// The harmony class of a word.
+#[derive( PartialEq, Eq, Clone, Copy, Debug )]
+pub enum HarmonyClass
+{
+    Back,
+    FrontUnrounded,
+    FrontRounded,
+    Mixed,
+}
+
+/// A vowel of the vowel projection.
+pub struct Vowel
+{
+    pub columns: Range<usize>,
+    pub back: bool,
+    pub rounded: bool,
+    pub neutral: bool,
+}
+
+// The last element of a tier (of a contour):
+fn last<'a>( tier: &[&'a str] ) -> &'a str
+{
+    tier.last().copied().unwrap_or( "" )
+}
+
+/// The columns of the last member of the word `table`, after
+/// its last compound or word boundary.
+pub fn last_member( table: &Table, tier_names: &[&str] ) -> Range<usize>
+{
+    let mut start = 0;
+    for columns in ipa::segments( table )
+    {
+        match ipa::features( table, tier_names, &columns ).boundary.first()
+        {
+            Some( &"compound" ) | Some( &"word" ) => { start = columns.end; }
+            _ => ()
+        }
+    }
+    start..table.number_of_columns()
+}
+
+/// The vowels of the last member of the word `table`.
+pub fn vowels( table: &Table, tier_names: &[&str] ) -> Vec<Vowel>
+{
+    let member = last_member( table, tier_names );
+    let mut result = Vec::new();
+    for columns in ipa::segments( table )
+    {
+        if columns.start < member.start
+        {
+            continue;
+        }
+        let features = ipa::features( table, tier_names, &columns );
+        match features.boundary.first()
+        {
+            Some( _ ) => (),
+            None if features.airflow == ["open"] =>
+            {
+                let back = last( &features.tongue ) == "back";
+                let rounded = last( &features.lips ) == "rounded";
+                let jaw = last( &features.jaw );
+                result.push( Vowel
+                             {
+                                 columns,
+                                 back,
+                                 rounded,
+                                 neutral: ! back && ! rounded &&
+                                     ( jaw == "high" || jaw == "mid-high" ),
+                             } );
+            }
+            None => ()
+        }
+    }
+    result
+}
+
+/// The harmony class of a vowel projection.
+pub fn classify( vowels: &[Vowel] ) -> HarmonyClass
+{
+    let harmonic: Vec<&Vowel> = vowels.iter().filter( | v | ! v.neutral ).collect();
+    let back = harmonic.iter().any( | v | v.back );
+    let front = harmonic.iter().any( | v | ! v.back );
+    match harmonic.last()
+    {
+        _ if back && front => HarmonyClass::Mixed,
+        _ if back => HarmonyClass::Back,
+        Some( v ) if v.rounded => HarmonyClass::FrontRounded,
+        _ => HarmonyClass::FrontUnrounded
+    }
+}
+
+/// The harmony class of a vowel projection for suffix selection:
+/// a mixed word takes the class of its last harmonic vowel
+/// (nüansznak, sofőrnek).
+pub fn suffix_class( vowels: &[Vowel] ) -> HarmonyClass
+{
+    match classify( vowels )
+    {
+        HarmonyClass::Mixed =>
+        {
+            let last = vowels.iter().rfind( | v | ! v.neutral ).unwrap();
+            if last.back { HarmonyClass::Back }
+            else if last.rounded { HarmonyClass::FrontRounded }
+            else { HarmonyClass::FrontUnrounded }
+        }
+        class => class
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::{HarmonyClass, suffix_class, vowels};
+    use crate::dialect::Dialect;
+    use crate::hungarian_grapheme::HungarianTable;
+    use crate::tabular::grapheme::GraphemeTabular;
+
+    fn harmony( converter: &HungarianTable, word: &'static str ) -> HarmonyClass
+    {
+        converter.harmony( &converter.from_string( word ).unwrap() )
+    }
+
+    #[test]
+    fn words_are_classified_by_their_vowels()
+    {
+        let converter = HungarianTable::new();
+        assert_eq!( harmony( &converter, "papír" ), HarmonyClass::Back );
+        assert_eq!( harmony( &converter, "víz" ), HarmonyClass::FrontUnrounded );
+        assert_eq!( harmony( &converter, "föld" ), HarmonyClass::FrontRounded );
+        assert_eq!( harmony( &converter, "öreg" ), HarmonyClass::FrontUnrounded );
+        assert_eq!( harmony( &converter, "sofőr" ), HarmonyClass::Mixed );
+        // the last member of a compound counts:
+        assert_eq!( harmony( &converter, "ház|tető" ), HarmonyClass::FrontRounded );
+    }
+
+    #[test]
+    fn mixed_words_take_the_suffixes_of_their_last_harmonic_vowel()
+    {
+        let converter = HungarianTable::new();
+        let tier_names = converter.all_tier_names();
+        let sofor = converter.from_string( "sofőr" ).unwrap();
+        assert_eq!( suffix_class( &vowels( &sofor, &tier_names ) ),
+                    HarmonyClass::FrontRounded );
+        let nuansz = converter.from_string( "nüansz" ).unwrap();
+        assert_eq!( suffix_class( &vowels( &nuansz, &tier_names ) ), HarmonyClass::Back );
+    }
+
+    #[test]
+    fn exceptions_apply_to_the_last_member()
+    {
+        let converter = HungarianTable::new();
+        assert_eq!( harmony( &converter, "híd" ), HarmonyClass::Back );
+        assert_eq!( harmony( &converter, "vas|híd" ), HarmonyClass::Back );
+        assert_eq!( harmony( &converter, "kis|szék" ), HarmonyClass::FrontUnrounded );
+        // only a whole member is an exception, not an ending:
+        assert_eq!( harmony( &converter, "szír" ), HarmonyClass::FrontUnrounded );
+        assert_eq!( harmony( &converter, "fa|szír" ), HarmonyClass::FrontUnrounded );
+    }
+
+    #[test]
+    fn closed_e_is_harmonic()
+    {
+        let converter = HungarianTable::with_dialect( Dialect::ClosedE );
+        assert_eq!( harmony( &converter, "kávë" ), HarmonyClass::Mixed );
+        assert_eq!( harmony( &converter, "kávé" ), HarmonyClass::Back );
+        assert_eq!( harmony( &converter, "szëm" ), HarmonyClass::FrontUnrounded );
+        let table = converter.from_string( "szëm" ).unwrap();
+        assert!( ! vowels( &table, &converter.all_tier_names() )[0].neutral );
+    }
+}
@@ -3,29 +3,136 @@
 // use std::fs::File;
 
 use std::collections::HashSet as Set;
+use std::collections::HashMap as Map;
 use std::iter::FromIterator;
+use std::ops::Range;
 
 use crate::tabular::grapheme;
 use crate::tabular::tabular::Tabular;
 
 use crate::tabular::table;
-use table::Table;
+use table::{Table, Tier};
 
-/// convert_graphemes::convert_graphemes<'a>( s: &'a str ) ->
-///     Result<String, String>;
+/// convert_graphemes::convert_graphemes(
+///     s: &[(char, Range<usize>)], ..., dialect: Dialect ) ->
+///     Result<Vec<(String, Range<usize>)>, String>;
 use crate::convert_graphemes;
+use convert_graphemes::Spanned;
+use crate::speech_style::{SpeechStyle, OptionalRule};
+use crate::segmentations;
+use crate::orthography::{Orthography, ChReading};
+use crate::name_orthography;
+use crate::normalization;
+use crate::spell_out::SpellOut;
+use crate::spelling;
+use crate::inventory;
+use crate::ipa::{self, IpaStyle};
+use crate::x_sampa;
+use crate::dialect::Dialect;
+use crate::syllable;
+use crate::stress;
+use crate::scansion::{self, Metre, Scansion};
+use crate::skeleton::{self, Skeleton};
+use crate::harmony::{self, HarmonyClass};
+use crate::suffix;
+use crate::natural_class::Specification;
+use crate::feature_chart::{self, ChartFormat};
 
-/**!
+/**
 A converter from Hungarian grapheme sequences,
 implements the `tabular::grapheme::GraphemeTabular` trait,
 with output type being `tabular::Table`.
+In the input, `+` marks a morpheme boundary and `|` a compound
+boundary (`ház+sor`, `kis|szék`): digraphs are not formed across
+them, but assimilations apply, and they are kept in the output
+on the "boundary" tier.
+A regional `Dialect` may be selected with `with_dialect`;
+`new` converts the standard variety.
 */
 pub struct HungarianTable
 {
-    number_of_tiers: usize,
+    // the tiers of converted tables:
     tier_names: Vec<&'static str>,
-    autosegments: Vec<&'static str>,
-    no_ocp: Set<usize>,         // indices into `tier_names`
+    // the tiers added after them by `syllabify` and `stress`:
+    prosodic_tier_names: Vec<&'static str>,
+    // the elements of each tier:
+    autosegments: Vec<(&'static str, &'static str)>,
+    // words whose final /h/ is pronounced:
+    final_h_exceptions: Set<String>,
+    speech_style: SpeechStyle,
+    orthography: Orthography,
+    ch_reading: ChReading,
+    // names spelled irregularly even for the name rules,
+    // with their present-day spelling:
+    name_exceptions: Map<String, String>,
+    spell_out: SpellOut,
+    dialect: Dialect,
+    // words (by their tables) whose harmony class is not
+    // the one of their vowels:
+    harmony_exceptions: Map<Table<'static>, HarmonyClass>,
+}
+
+// Segments of the inventory with their tables:
+type SegmentTables = Vec<(&'static str, Table<'static>)>;
+
+// The number of decompositions of a table into segments
+// considered by `to_spellings`:
+const MAX_DECOMPOSITIONS: usize = 64;
+
+// Stems with neutral vowels only taking back suffixes (hídnak):
+const BACK_NEUTRAL_STEMS: [&str; 9] = [
+    "híd", "íj", "ír", "nyíl", "szíj", "cél", "derék", "csík", "zsír",
+];
+
+/// A reading of an orthographically ambiguous string:
+/// `segmentation` is the input with `+` inserted where
+/// a multigraph is read as separate graphemes.
+pub struct Reading
+{
+    pub segmentation: String,
+    pub table: Table<'static>,
+}
+
+/// A converted phrase: the tables of its words, and the table of
+/// the whole phrase, with a word boundary column between words.
+/// Processes applying across word boundaries are reflected in both.
+pub struct Phrase
+{
+    pub words: Vec<Table<'static>>,
+    pub table: Table<'static>,
+}
+
+/// A phonemic segment (or boundary) in the internal notation of
+/// `from_grapheme`, with the byte span of the input it comes from:
+/// a digraph spans several letters, the segments of "x" (k, s)
+/// share the span of that letter.  `uppercase` tells whether
+/// a letter of the span is uppercase.
+pub struct Segment
+{
+    pub symbol: String,
+    pub span: Range<usize>,
+    pub uppercase: bool,
+}
+
+/// The result of `transcribe`: the segments, the table
+/// returned by `from_string`, the input span of each
+/// of its columns, and the punctuation removed from the input
+/// with its byte offsets.
+pub struct Transcription
+{
+    pub segments: Vec<Segment>,
+    pub table: Table<'static>,
+    pub column_spans: Vec<Range<usize>>,
+    pub punctuation: Vec<(usize, char)>,
+}
+
+/// A pronunciation variant, labelled with the optional
+/// rules applied to obtain it.
+#[derive( Debug, Clone, PartialEq )]
+pub struct Variant
+{
+    pub rules: Vec<OptionalRule>,
+    pub table: Table<'static>,
 }
 
 /// Implementation of a converter trait
@@ -35,112 +142,907 @@ impl grapheme::GraphemeTabular<Table<'static>> for HungarianTable
 
     fn new() -> Self
     {
-        HungarianTable
+        let mut converter = HungarianTable
         {
-            number_of_tiers: 7,
             tier_names: vec![
                 "tongue", "jaw", "lips", "voice", "airflow",
-                "time", "eventuality"],
+                "time", "eventuality", "boundary"],
+            prosodic_tier_names: vec!["syllable", "stress"],
             autosegments: vec![
-   // Tongue:
-        "neutral",              // also Jaw and Lips
-        "front",
-        "back",
-        "dentialveolar",
-        "coronal",
-        "palatal",
-        "velar",
-        // Jaw:
-        "extra-low",
-        "low",
-        "mid",
-        "mid-high",
-        "high",
-        // Lips:
-        "rounded",
-        "unrounded",
-        "closed",
-        "approximate",
-        "lower-to-teeth",
-        // Voice:
-        "voiced",
-        "voiceless",
-        // Airflow:
-        "open",
-        "slit",
-        "stop",
-        "lateral",
-        "nasal",
-        "approximate",
-        "trill",
-        // Time:
-        "X",
-        // Eventuality:
-        "state",
-        "transition",
-        "event"
+                // the neutral element may be on tongue, jaw and lips:
+                ("tongue", "neutral"), ("tongue", "front"), ("tongue", "back"),
+                ("tongue", "dentialveolar"), ("tongue", "coronal"),
+                ("tongue", "palatal"), ("tongue", "velar"),
+                ("jaw", "neutral"), ("jaw", "extra-low"), ("jaw", "low"),
+                ("jaw", "mid"), ("jaw", "mid-high"), ("jaw", "high"),
+                ("lips", "neutral"), ("lips", "lips"), ("lips", "rounded"),
+                ("lips", "unrounded"), ("lips", "closed"),
+                ("lips", "approximate"), ("lips", "lower-to-teeth"),
+                ("voice", "voiced"), ("voice", "voiceless"),
+                ("airflow", "open"), ("airflow", "slit"), ("airflow", "stop"),
+                ("airflow", "lateral"), ("airflow", "nasal"),
+                ("airflow", "approximate"), ("airflow", "trill"),
+                ("time", "X"),
+                ("eventuality", "state"), ("eventuality", "transition"),
+                ("eventuality", "event"),
+                ("boundary", "morpheme"), ("boundary", "compound"),
+                ("boundary", "word"),
+                ("syllable", "onset"), ("syllable", "nucleus"),
+                ("syllable", "coda"),
+                ("stress", "primary"), ("stress", "secondary"),
+                ("stress", "unstressed"),
             ],
-            no_ocp: Set::from_iter( vec![5, 6] ),    // time
-        }
+            final_h_exceptions: Set::from_iter(
+                vec!["sah", "allah"].into_iter().map( String::from ) ),
+            speech_style: SpeechStyle::Careful,
+            orthography: Orthography::Standard,
+            ch_reading: ChReading::H,
+            name_exceptions: Map::from_iter(
+                vec![("kossuth", "kosut"),
+                     ("dessewffy", "dezsőfi"),
+                     ("ghyczy", "gicsi")].into_iter().
+                map( | (n, s) | (n.to_string(), s.to_string()) ) ),
+            spell_out: SpellOut::new(),
+            dialect: Dialect::Standard,
+            harmony_exceptions: Map::new(),
+        };
+        converter.add_back_neutral_stems();
+        converter
     }
     
     fn tier_name_to_tier_index(
         &self, tier_name: &Self::TierName )
         -> usize
     {
-        return <[_]>::binary_search( &self.tier_names, &tier_name ).ok().unwrap()
+        <[_]>::iter( &self.all_tier_names() ).
+            position( | name | name == tier_name ).unwrap()
     }
     fn tier_index_to_tier_name(
         &self, tier_index: usize )
         -> Self::TierName
     {
-        return self.tier_names[tier_index]
+        self.all_tier_names()[tier_index]
     }
     fn from_string( &self, graphemes: &'static str )
                          -> Result<Table<'static>, String>
     {
-        match convert_graphemes::convert_graphemes( graphemes )
+        self.transcribe( graphemes ).map( | t | t.table )
+    }
+}
+
+/// Traitless methods of a converter structure:
+impl HungarianTable
+{
+    /// The names of the tiers of converted tables, followed by
+    /// those of the tiers added by `syllabify` and `stress`.
+    pub fn all_tier_names( &self ) -> Vec<&'static str>
+    {
+        self.tier_names.iter().chain( &self.prosodic_tier_names ).copied().collect()
+    }
+
+    /// A converter for the regional variety `dialect`.
+    pub fn with_dialect( dialect: Dialect ) -> Self
+    {
+        let mut table = <HungarianTable as grapheme::GraphemeTabular<_>>::new();
+        table.dialect = dialect;
+        // the tables of the exceptions in the dialect:
+        table.harmony_exceptions.clear();
+        table.add_back_neutral_stems();
+        table
+    }
+
+    fn add_back_neutral_stems( &mut self )
+    {
+        for stem in BACK_NEUTRAL_STEMS.iter()
+        {
+            self.add_harmony_exception( stem, HarmonyClass::Back ).ok();
+        }
+    }
+
+    /// Register a word whose harmony class is not the one of its vowels.
+    pub fn add_harmony_exception( &mut self, word: &str, class: HarmonyClass )
+        -> Result<(), String>
+    {
+        let table = self.transcribe( word )?.table;
+        self.harmony_exceptions.insert( table, class );
+        Ok( () )
+    }
+
+    /// Register a word whose final /h/ is not deleted
+    /// (in any letter case: Pech, pech).
+    pub fn add_final_h_exception( &mut self, word: &str )
+    {
+        self.final_h_exceptions.insert( word.to_lowercase() );
+    }
+
+    /// Select the speech style (careful by default, no optional
+    /// rule applying).
+    pub fn set_speech_style( &mut self, style: SpeechStyle )
+    {
+        self.speech_style = style;
+    }
+
+    pub fn set_orthography( &mut self, orthography: Orthography )
+    {
+        self.orthography = orthography;
+    }
+
+    pub fn set_ch_reading( &mut self, reading: ChReading )
+    {
+        self.ch_reading = reading;
+    }
+
+    // The segment of the digraph "ch", if loan digraphs are read:
+    fn ch_segment( &self ) -> Option<&'static str>
+    {
+        if self.orthography != Orthography::Loanwords
+        {
+            return None;
+        }
+        match self.ch_reading
+        {
+            ChReading::H => Some( "x" ),
+            ChReading::Cs => Some( "č" ),
+        }
+    }
+
+    /// Register the present-day spelling of a name
+    /// that the name rules get wrong.
+    pub fn add_name_exception( &mut self, name: &str, spelling: &str )
+    {
+        self.name_exceptions.insert(
+            name.to_lowercase(), spelling.to_string() );
+    }
+
+    // The input normalized and in present-day orthography,
+    // each character with its span in `graphemes`, and the record
+    // of the normalization (letter case, punctuation):
+    fn modernize( &self, graphemes: &str )
+        -> Result<(Spanned, normalization::Normalized), String>
+    {
+        let normalized = normalization::normalize( graphemes )?;
+        let word = self.modernize_letters( &normalized )?;
+        Ok( ( word, normalized ) )
+    }
+
+    fn modernize_letters( &self, normalized: &normalization::Normalized )
+        -> Result<Spanned, String>
+    {
+        let word: Spanned =
+            normalized.text.chars().zip( normalized.spans.iter().cloned() ).collect();
+        match self.orthography
+        {
+            Orthography::Standard | Orthography::Loanwords => Ok( word ),
+            Orthography::Names =>
+            {
+                match self.name_exceptions.get( &normalized.text )
+                {
+                    Some( spelling ) =>
+                    {
+                        // the whole name is the source of each letter:
+                        let whole = match ( word.first(), word.last() )
+                        {
+                            ( Some( first ), Some( last ) ) =>
+                                first.1.start..last.1.end,
+                            _ => 0..0
+                        };
+                        Ok( spelling.chars().map( | c | ( c, whole.clone() ) ).
+                            collect() )
+                    }
+                    None =>
+                        convert_graphemes::apply(
+                            &word, name_orthography::name_orthography::input ).
+                        map( convert_graphemes::characters ).
+                        map_err( | e | format!( "name_orthography: {}", e ) )
+                }
+            }
+        }
+    }
+
+    /// The phonemic transcription of `graphemes`: its segments
+    /// and its table, tracing each segment and each column of the
+    /// table back to the letters of `graphemes` it comes from.
+    pub fn transcribe( &self, graphemes: &str ) -> Result<Transcription, String>
+    {
+        let ( word, normalized ) = self.modernize( graphemes )?;
+        let keep_final_h =
+            self.final_h_exceptions.contains( &convert_graphemes::text( &word ) );
+        match convert_graphemes::convert_graphemes(
+            &word, &[keep_final_h], &self.speech_style.optional_rules(),
+            self.ch_segment(), self.dialect )
         {
             Ok( gr ) =>
             {
-                let mut result;
-                match HungarianTable::from_grapheme( &gr[0] )
+                let table = HungarianTable::from_segments( &gr )?;
+                let mut column_spans = Vec::new();
+                for ( symbol, span ) in &gr
                 {
-                    Ok( tab ) => { result = tab; }
-                    Err( e ) => { return Err( e ); }
+                    let width =
+                        HungarianTable::from_grapheme( symbol )?.number_of_columns();
+                    for _ in 0..width
+                    {
+                        column_spans.push( span.clone() );
+                    }
                 }
-                for i in 1..<[_]>::len( &gr )
+                let uppercase = | span: &Range<usize> |
+                    normalized.spans.iter().zip( &normalized.uppercase ).
+                    any( | (letter, upper) |
+                         *upper && letter.start < span.end && span.start < letter.end );
+                Ok( Transcription
+                    {
+                        segments: gr.into_iter().
+                            map( | (symbol, span) |
+                                 Segment { uppercase: uppercase( &span ), symbol, span } ).
+                            collect(),
+                        table,
+                        column_spans,
+                        punctuation: normalized.punctuation.clone(),
+                    } )
+            }
+            Err( e ) => Err( format!( "Parse error: {}", &e ) )
+        }
+    }
+
+    /// All pronunciation variants of `graphemes` in the current
+    /// speech style: each subset of the style's optional rules
+    /// is applied, and variants identical to one obtained with
+    /// fewer rules are dropped.  The first variant is the careful
+    /// one; `from_string` returns the one with all the rules.
+    pub fn from_string_variants( &self, graphemes: &str )
+        -> Result<Vec<Variant>, String>
+    {
+        let ( word, _ ) = self.modernize( graphemes )?;
+        let keep_final_h =
+            self.final_h_exceptions.contains( &convert_graphemes::text( &word ) );
+        let optional = self.speech_style.optional_rules();
+        let mut rule_sets: Vec<Vec<OptionalRule>> = Vec::new();
+        for i in 0..usize::pow( 2, optional.len() as u32 )
+        {
+            rule_sets.push(
+                (0..optional.len()).
+                filter( | j | i & ( 1 << j ) != 0 ).
+                map( | j | optional[j] ).
+                collect() );
+        }
+        rule_sets.sort_by_key( | rules | rules.len() );
+
+        let mut seen: Set<Vec<String>> = Set::new();
+        let mut result = Vec::new();
+        for rules in rule_sets
+        {
+            match convert_graphemes::convert_graphemes(
+                &word, &[keep_final_h], &rules,
+                self.ch_segment(), self.dialect )
+            {
+                Ok( gr ) =>
                 {
-                    match HungarianTable::from_grapheme( &gr[i] )
+                    if seen.insert( HungarianTable::symbols( &gr ) )
                     {
-                        Ok( mut tab ) =>
-                        {
-                            result =
-                                Tabular::concatenate(
-                                    &result,
-                                    &mut tab );
-                        }
-                        Err( e ) =>
+                        let table = HungarianTable::from_segments( &gr )?;
+                        result.push( Variant { rules, table } );
+                    }
+                }
+                Err( e ) => { return Err( format!( "Parse error: {}", &e ) ); }
+            }
+        }
+        Ok( result )
+    }
+
+    /// All distinct tables that `graphemes` may stand for,
+    /// obtained from every grapheme segmentation that can be
+    /// converted (at most `segmentations::MAX_READINGS`).  Readings
+    /// differing only in their boundaries count as one.  If
+    /// a `lexicon` of morphemes is given, only readings consisting
+    /// of its morphemes are kept.  The greedy reading (the one
+    /// `from_string` returns) comes first if it survives.
+    pub fn from_string_readings(
+        &self, graphemes: &str, lexicon: Option<&Set<String>> )
+        -> Result<Vec<Reading>, String>
+    {
+        let word = convert_graphemes::text( &self.modernize( graphemes )?.0 );
+        let keep_final_h = self.final_h_exceptions.contains( &word );
+        let mut seen: Set<Table<'static>> = Set::new();
+        let mut result = Vec::new();
+        for segmentation in segmentations::segmentations( &word )
+        {
+            if let Some( morphemes ) = lexicon
+            {
+                if ! segmentation.split( ['+', '|'] ).
+                    all( | m | morphemes.contains( m ) )
+                {
+                    continue;
+                }
+            }
+            let table =
+                convert_graphemes::convert_graphemes(
+                    &convert_graphemes::spanned( &segmentation ),
+                    &[keep_final_h], &self.speech_style.optional_rules(),
+                    self.ch_segment(), self.dialect ).
+                and_then( | gr | HungarianTable::from_segments( &gr ) ).
+                map_err( | e | format!( "Parse error in {}: {}", segmentation, &e ) )?;
+            if seen.insert( self.without_boundaries( &table ) )
+            {
+                result.push( Reading { segmentation, table } );
+            }
+        }
+        Ok( result )
+    }
+
+    // `table` without its boundary columns:
+    fn without_boundaries( &self, table: &Table<'static> ) -> Table<'static>
+    {
+        let mut result: Option<Table<'static>> = None;
+        for columns in ipa::segments( table )
+        {
+            if ! ipa::features( table, &self.tier_names, &columns ).boundary.is_empty()
+            {
+                continue;
+            }
+            let part = table.columns( columns.start, columns.end );
+            result = Some( match result
+                           {
+                               Some( prefix ) => Tabular::concatenate( &prefix, &part ),
+                               None => part
+                           } );
+        }
+        result.unwrap_or_else( || table.clone() )
+    }
+
+    /// Convert a text consisting of whitespace-separated words,
+    /// applying voice assimilation, palatalization and geminate
+    /// simplification across word boundaries as well
+    /// (két gyerek -> kéď#ďerek, the word boundary staying
+    /// between the halves of the geminate).
+    pub fn from_phrase( &self, text: &str ) -> Result<Phrase, String>
+    {
+        // the words with spans in `text`, word boundaries
+        // spanning the whitespace between words:
+        let mut phrase: Spanned = Vec::new();
+        let mut keep_final_h: Vec<bool> = Vec::new();
+        let mut previous_end = 0;
+        for token in text.split_whitespace()
+        {
+            let offset = token.as_ptr() as usize - text.as_ptr() as usize;
+            let ( word, _ ) = self.modernize( token )?;
+            if ! word.is_empty()
+            {
+                if ! phrase.is_empty()
+                {
+                    phrase.push( ( '#', previous_end..offset ) );
+                }
+                keep_final_h.push(
+                    self.final_h_exceptions.contains(
+                        &convert_graphemes::text( &word ) ) );
+                phrase.extend(
+                    word.into_iter().
+                    map( | (c, span) |
+                         ( c, span.start + offset..span.end + offset ) ) );
+                previous_end = offset + token.len();
+            }
+        }
+        match convert_graphemes::convert_graphemes(
+            &phrase, &keep_final_h, &self.speech_style.optional_rules(),
+            self.ch_segment(), self.dialect )
+        {
+            Ok( gr ) =>
+            {
+                let mut word_tables = Vec::new();
+                for word in gr.split( | (segment, _) | segment == "#" )
+                {
+                    word_tables.push( HungarianTable::from_segments( word )? );
+                }
+                Ok( Phrase
+                    {
+                        words: word_tables,
+                        table: HungarianTable::from_segments( &gr )?,
+                    } )
+            }
+            Err( e ) => Err( format!( "Parse error: {}", &e ) )
+        }
+    }
+
+    /// Convert running text: numerals, abbreviations and acronyms
+    /// are spelled out first, then the text is converted as a phrase.
+    pub fn from_text( &self, text: &str ) -> Result<Phrase, String>
+    {
+        self.from_phrase( &self.spell_out.spell_out( text ) )
+    }
+
+    /// Register an abbreviation to be expanded by `from_text`.
+    pub fn add_abbreviation( &mut self, abbreviation: &str, expansion: &str )
+    {
+        self.spell_out.add_abbreviation( abbreviation, expansion );
+    }
+
+    fn symbols( segments: &[(String, Range<usize>)] ) -> Vec<String>
+    {
+        segments.iter().map( | (symbol, _) | symbol.clone() ).collect()
+    }
+
+    /// Orthographic strings converted into `table` by `from_string`
+    /// (or by `from_phrase` if the table contains word boundaries,
+    /// which are spelled as spaces).  Letters with several possible
+    /// spellings (j/ly), geminates of digraphs (ssz) and assimilated
+    /// clusters spelled etymologically (kézből, átjáró) give several
+    /// candidates; the candidates of all the decompositions of the
+    /// table into segments are ranked together by `frequencies`
+    /// if given (missing strings counting as 0), then by preferring
+    /// usual spellings.
+    pub fn to_spellings(
+        &self, table: &Table<'static>, frequencies: Option<&Map<String, usize>> )
+        -> Result<Vec<String>, String>
+    {
+        let decompositions = HungarianTable::decompositions( table );
+        if decompositions.is_empty()
+        {
+            return Err( "The table is not a sequence of segments".to_string() );
+        }
+        self.ranked_spellings( &decompositions, table, frequencies )
+    }
+
+    /// Orthographic strings of a segment sequence in the notation
+    /// of `from_grapheme` (the symbols of `Transcription::segments`),
+    /// ranked as by `to_spellings`.
+    pub fn spell_segments(
+        &self, segments: &[String], frequencies: Option<&Map<String, usize>> )
+        -> Result<Vec<String>, String>
+    {
+        let spanned: Vec<(String, Range<usize>)> =
+            segments.iter().map( | s | ( s.clone(), 0..0 ) ).collect();
+        let target = HungarianTable::from_segments( &spanned )?;
+        self.ranked_spellings( &[segments.to_vec()], &target, frequencies )
+    }
+
+    // The candidates of the segment sequences `decompositions`
+    // converting back into `target`, each candidate converted once,
+    // with its lowest cost:
+    fn ranked_spellings(
+        &self, decompositions: &[Vec<String>], target: &Table<'static>,
+        frequencies: Option<&Map<String, usize>> )
+        -> Result<Vec<String>, String>
+    {
+        let mut checked: Map<String, bool> = Map::new();
+        let mut result: Vec<(String, usize)> = Vec::new();
+        for segments in decompositions
+        {
+            for ( spelling, cost ) in spelling::candidates( segments )?
+            {
+                let converts = match checked.get( &spelling )
+                {
+                    Some( converts ) => *converts,
+                    None =>
+                    {
+                        let table =
+                            if spelling.contains( ' ' )
+                            {
+                                self.from_phrase( &spelling ).map( | p | p.table )
+                            }
+                            else
+                            {
+                                self.transcribe( &spelling ).map( | t | t.table )
+                            };
+                        let converts = table.ok().as_ref() == Some( target );
+                        checked.insert( spelling.clone(), converts );
+                        converts
+                    }
+                };
+                if ! converts
+                {
+                    continue;
+                }
+                match result.iter_mut().find( | (s, _) | *s == spelling )
+                {
+                    Some( found ) => { found.1 = found.1.min( cost ); }
+                    None => result.push( ( spelling, cost ) )
+                }
+            }
+        }
+        result.sort_by_key( | (_, cost) | *cost );
+        if let Some( frequencies ) = frequencies
+        {
+            // stable: equally frequent candidates stay ordered by cost
+            result.sort_by_key( | (s, _) |
+                std::cmp::Reverse( frequencies.get( s ).copied().unwrap_or( 0 ) ) );
+        }
+        Ok( result.into_iter().map( | (s, _) | s ).collect() )
+    }
+
+    /// `table` with a syllable tier added, its timing slots
+    /// being onsets, nuclei and codas (see `syllable`).
+    pub fn syllabify( &self, table: &Table<'static> )
+        -> Result<Table<'static>, String>
+    {
+        let syllable_tier =
+            grapheme::GraphemeTabular::tier_name_to_tier_index( self, &"syllable" );
+        if table.tiers.len() > syllable_tier
+        {
+            return Err( "The table already has a syllable tier".to_string() );
+        }
+        let tier = syllable::syllable_tier( table, &self.all_tier_names() )?;
+        let mut result = table.clone();
+        result.tiers.push( tier );
+        Ok( result )
+    }
+
+    /// `table` with a stress tier added (and a syllable tier if it
+    /// has none), see `stress`.  The words flagged in
+    /// `unstressed_words` get no stress.
+    pub fn stress( &self, table: &Table<'static>, unstressed_words: &[bool] )
+        -> Result<Table<'static>, String>
+    {
+        let syllable_tier =
+            grapheme::GraphemeTabular::tier_name_to_tier_index( self, &"syllable" );
+        let mut result =
+            if table.tiers.len() > syllable_tier { table.clone() }
+            else { self.syllabify( table )? };
+        if result.tiers.len() > syllable_tier + 1
+        {
+            return Err( "The table already has a stress tier".to_string() );
+        }
+        let tier = stress::stress_tier( &result, &self.all_tier_names(), unstressed_words )?;
+        result.tiers.push( tier );
+        Ok( result )
+    }
+
+    /// The table of a phrase (see `from_phrase`) with syllable and
+    /// stress tiers; if `destress_clitics` is set, the articles
+    /// and clitics of `stress::CLITICS` are unstressed.
+    pub fn from_phrase_stressed( &self, text: &str, destress_clitics: bool )
+        -> Result<Table<'static>, String>
+    {
+        let mut unstressed_words = Vec::new();
+        for token in text.split_whitespace()
+        {
+            let word = convert_graphemes::text( &self.modernize( token )?.0 );
+            if ! word.is_empty()
+            {
+                unstressed_words.push(
+                    destress_clitics && stress::CLITICS.contains( &word.as_str() ) );
+            }
+        }
+        let phrase = self.from_phrase( text )?;
+        self.stress( &phrase.table, &unstressed_words )
+    }
+
+    /// The quantitative scansion of a verse line according to
+    /// `metre`; the line is converted as a phrase.
+    pub fn scan_line( &self, line: &str, metre: Metre )
+        -> Result<Scansion, String>
+    {
+        let phrase = self.from_phrase( line )?;
+        Ok( scansion::scan( &phrase.table, &self.all_tier_names(), metre ) )
+    }
+
+    /// The CV skeleton of a table of this converter, with its
+    /// doubly linked segments and morae.
+    pub fn skeleton( &self, table: &Table ) -> Result<Skeleton, String>
+    {
+        skeleton::skeleton( table, &self.all_tier_names() )
+    }
+
+    /// The harmony class of a word table of this converter: the
+    /// class registered as an exception for its last compound member
+    /// (híd, vas|híd, but not szír), or the class of its vowels
+    /// (see `harmony`).
+    pub fn harmony( &self, table: &Table<'static> ) -> HarmonyClass
+    {
+        let tier_names = self.all_tier_names();
+        let member = harmony::last_member( table, &tier_names );
+        let last =
+            Table { tiers: table.columns( member.start, member.end ).tiers.
+                    into_iter().take( self.tier_names.len() ).collect() };
+        if let Some( class ) = self.harmony_exceptions.get( &last )
+        {
+            return *class;
+        }
+        harmony::classify( &harmony::vowels( table, &tier_names ) )
+    }
+
+    /// The table of `stem` (a word table of this converter) with
+    /// the suffix `template` (see `suffix`) attached, its allomorph
+    /// chosen by the harmony class of the stem and the processes of
+    /// the seam applied.  Tiers added to the stem are dropped.
+    pub fn attach_suffix( &self, stem: &Table<'static>, template: &str )
+        -> Result<Table<'static>, String>
+    {
+        let class =
+            match self.harmony( stem )
+            {
+                HarmonyClass::Mixed =>
+                    harmony::suffix_class(
+                        &harmony::vowels( stem, &self.all_tier_names() ) ),
+                class => class
+            };
+        let suffix = suffix::allomorph( template, class )?;
+        let suffix_segments: Vec<String> =
+            self.transcribe( &suffix )?.segments.into_iter().
+            map( | segment | segment.symbol ).
+            collect();
+        let converted =
+            Table { tiers: stem.tiers.iter().take( self.tier_names.len() ).
+                    cloned().collect() };
+        // the seam: the last two segments of the stem, the columns
+        // before them kept as they are:
+        let spans = ipa::segments( &converted );
+        let seam = &spans[spans.len().saturating_sub( 2 )..];
+        let mut seam_segments = Vec::new();
+        for span in seam
+        {
+            let symbol =
+                HungarianTable::segment_symbol( &converted.columns( span.start, span.end ) ).
+                ok_or_else( || "The stem table is not a sequence of segments".to_string() )?;
+            seam_segments.push( symbol.to_string() );
+        }
+        let mut result =
+            match seam.first()
+            {
+                Some( span ) if span.start > 0 => Some( converted.columns( 0, span.start ) ),
+                Some( _ ) => None,
+                None => { return Err( "Empty stem".to_string() ); }
+            };
+        for segment in suffix::attach( &seam_segments, &suffix_segments )
+        {
+            let table = HungarianTable::from_grapheme( &segment )?;
+            result =
+                Some( match result
+                      {
+                          Some( result ) => Tabular::concatenate( &result, &table ),
+                          None => table
+                      } );
+        }
+        result.ok_or_else( || "Empty stem".to_string() )
+    }
+
+    // The symbol of the segment or boundary `table` stands for,
+    // the first one in the order of `inventory::symbols` if several
+    // have the same table:
+    fn segment_symbol( table: &Table<'static> ) -> Option<&'static str>
+    {
+        inventory::symbols().
+            find( | symbol | HungarianTable::from_grapheme( symbol ).
+                  is_ok_and( | t | t == *table ) )
+    }
+
+    // The segments of the inventory and their tables:
+    fn inventory( &self ) -> Result<SegmentTables, String>
+    {
+        let mut result = Vec::new();
+        for symbol in inventory::SEGMENTS.iter()
+        {
+            result.push( ( *symbol, HungarianTable::from_grapheme( symbol )? ) );
+        }
+        Ok( result )
+    }
+
+    /// The segments of the inventory covered by the feature
+    /// specification `spec` (see `natural_class`).
+    pub fn natural_class( &self, spec: &str ) -> Result<Vec<&'static str>, String>
+    {
+        let spec =
+            Specification::parse( spec, &self.autosegments )?.
+            table( &self.tier_names, &self.autosegments )?;
+        Ok( self.inventory()?.into_iter().
+            filter( | (_, table) | spec.subsumes( table ) ).
+            map( | (symbol, _) | symbol ).
+            collect() )
+    }
+
+    /// The most specific feature specification covering `segments`,
+    /// if it covers no other segment of the inventory.  Contours are
+    /// not specified, so there is none for the affricates (c, č).
+    pub fn class_specification( &self, segments: &[&str] )
+        -> Result<Specification, String>
+    {
+        let inventory = self.inventory()?;
+        let mut tables = Vec::new();
+        for segment in segments
+        {
+            match inventory.iter().find( | (symbol, _) | symbol == segment )
+            {
+                Some( (_, table) ) => tables.push( table ),
+                None => { return Err( format!( "Unknown segment \"{}\"", segment ) ); }
+            }
+        }
+        let covers = | spec: &Specification |
+            spec.table( &self.tier_names, &self.autosegments ).
+            map( | spec | tables.iter().all( | table | spec.subsumes( table ) ) );
+        // the features subsuming every segment on their own:
+        let mut spec = Specification { features: Vec::new() };
+        let candidates =
+            self.autosegments.iter().
+            filter( | (tier, _) | self.tier_names.contains( tier ) && *tier != "time" ).
+            copied().
+            chain( vec![( "time", "X" ), ( "time", "X X" )] );
+        for ( tier, element ) in candidates
+        {
+            let feature = ( tier.to_string(), element.to_string() );
+            if covers( &Specification { features: vec![feature.clone()] } )?
+            {
+                spec.features.push( feature );
+            }
+        }
+        if ! covers( &spec )?
+        {
+            return Err( format!( "No specification covers {}", segments.join( " " ) ) );
+        }
+        let table = spec.table( &self.tier_names, &self.autosegments )?;
+        let others: Vec<&str> =
+            inventory.iter().
+            filter( | (symbol, t) | table.subsumes( t ) && ! segments.contains( symbol ) ).
+            map( | (symbol, _) | *symbol ).
+            collect();
+        if others.is_empty() { Ok( spec ) }
+        else
+        {
+            Err( format!( "Not a natural class: {} also covers {}",
+                          spec, others.join( " " ) ) )
+        }
+    }
+
+    // The segments of the inventory with their tables, and the
+    // tiers of segments (the boundary tier excluded):
+    fn segment_chart_rows( &self ) -> Result<(SegmentTables, Vec<&'static str>), String>
+    {
+        let rows = self.inventory()?;
+        let tiers =
+            self.tier_names.iter().
+            filter( | name | **name != "boundary" ).
+            copied().collect();
+        Ok( ( rows, tiers ) )
+    }
+
+    /// The segments of `from_grapheme` by their elements on each tier.
+    pub fn feature_chart( &self, format: ChartFormat ) -> Result<String, String>
+    {
+        let ( rows, tiers ) = self.segment_chart_rows()?;
+        Ok( feature_chart::chart( &rows, &tiers, format ) )
+    }
+
+    /// The segments of `from_grapheme` by the autosegments of
+    /// their tiers, as binary features.
+    pub fn binary_feature_chart( &self, format: ChartFormat ) -> Result<String, String>
+    {
+        let ( rows, tiers ) = self.segment_chart_rows()?;
+        let autosegments: Vec<(&str, &str)> =
+            self.autosegments.iter().
+            filter( | (tier, _) | tiers.contains( tier ) ).
+            copied().collect();
+        Ok( feature_chart::binary_chart( &rows, &self.tier_names, &autosegments, format ) )
+    }
+
+    /// The column ranges of the syllables of a table of this converter.
+    pub fn syllables( &self, table: &Table )
+        -> Result<Vec<Range<usize>>, String>
+    {
+        syllable::syllables( table, &self.all_tier_names() )
+    }
+
+    /// The IPA transcription of a table of this converter.
+    pub fn to_ipa( &self, table: &Table, style: IpaStyle )
+        -> Result<String, String>
+    {
+        ipa::table_to_ipa( table, &self.all_tier_names(), style )
+    }
+
+    /// The X-SAMPA transcription of a table of this converter.
+    pub fn to_x_sampa( &self, table: &Table, style: IpaStyle )
+        -> Result<String, String>
+    {
+        x_sampa::ipa_to_x_sampa( &self.to_ipa( table, style )? )
+    }
+
+    /// The IPA transcription of a segment sequence in the notation
+    /// of `from_grapheme`.
+    pub fn segments_to_ipa( &self, segments: &[String], style: IpaStyle )
+        -> Result<String, String>
+    {
+        let spanned: Vec<(String, Range<usize>)> =
+            segments.iter().map( | s | ( s.clone(), 0..0 ) ).collect();
+        self.to_ipa( &HungarianTable::from_segments( &spanned )?, style )
+    }
+
+    // All ways of cutting `table` into the tables of segments:
+    fn decompositions( table: &Table<'static> ) -> Vec<Vec<String>>
+    {
+        // one symbol for each distinct table:
+        let mut segment_tables: Vec<(&str, Table<'static>)> = Vec::new();
+        for symbol in inventory::symbols()
+        {
+            if let Ok( t ) = HungarianTable::from_grapheme( symbol )
+            {
+                if ! segment_tables.iter().any( | (_, other) | *other == t )
+                {
+                    segment_tables.push( ( symbol, t ) );
+                }
+            }
+        }
+        let width = table.number_of_columns();
+        // decompositions of the columns from each position to the end,
+        // at most `MAX_DECOMPOSITIONS` of them:
+        let mut rest: Vec<Vec<Vec<String>>> = vec![Vec::new(); width + 1];
+        rest[width].push( Vec::new() );
+        for start in ( 0..width ).rev()
+        {
+            let mut here = Vec::new();
+            for ( symbol, t ) in &segment_tables
+            {
+                let end = start + t.number_of_columns();
+                if end <= width && table.columns( start, end ) == *t
+                {
+                    for tail in &rest[end]
+                    {
+                        if here.len() == MAX_DECOMPOSITIONS
                         {
-                            return Err( e );
+                            break;
                         }
+                        let mut segments = vec![symbol.to_string()];
+                        segments.extend( tail.iter().cloned() );
+                        here.push( segments );
                     }
                 }
-                Ok( result )
             }
-            Err( e ) =>
+            rest[start] = here;
+        }
+        rest.swap_remove( 0 )
+    }
+
+    /// Concatenate the tables of a sequence of segments.
+    fn from_segments( segments: &[(String, Range<usize>)] )
+        -> Result<Table<'static>, String>
+    {
+        if segments.is_empty()
+        {
+            return Err( "Empty segment sequence".to_string() );
+        }
+        let mut result;
+        match HungarianTable::from_grapheme( &segments[0].0 )
+        {
+            Ok( tab ) => { result = tab; }
+            Err( e ) => { return Err( e ); }
+        }
+        for ( segment, _ ) in &segments[1..]
+        {
+            match HungarianTable::from_grapheme( segment )
             {
-                Err( format!( "Parse error: {}", &e ) )
-                // std::process::exit( 1 );
+                Ok( tab ) =>
+                {
+                    result = Tabular::concatenate( &result, &tab );
+                }
+                Err( e ) =>
+                {
+                    return Err( e );
+                }
             }
         }
+        Ok( result )
     }
-}
 
-/// Traitless methods of a converter structure:
-impl HungarianTable
-{
-    pub fn from_grapheme<'a>( grapheme: &'a str ) ->
+    /// The table of a segment, or of a boundary (`+`: morpheme,
+    /// `|`: compound, `#`: word), which occupies a column of its own,
+    /// empty on all tiers except "boundary".
+    pub fn from_grapheme( grapheme: &str ) ->
+        Result<Table<'static>, String>
+    {
+        match grapheme
+        {
+            "+" =>
+                Table::from_str( "_, _, _, _, _, _, _, morpheme;" ),
+            "|" =>
+                Table::from_str( "_, _, _, _, _, _, _, compound;" ),
+            "#" =>
+                Table::from_str( "_, _, _, _, _, _, _, word;" ),
+            _ =>
+            {
+                let mut table = HungarianTable::segment_table( grapheme )?;
+                let width = table.number_of_columns();
+                table.tiers.push( Tier::new_gap( width ) );
+                Ok( table )
+            }
+        }
+    }
+
+    // Tables of segments on all tiers except "boundary":
+    fn segment_table( grapheme: &str ) ->
         Result<Table<'static>, String>
     {
         match grapheme
@@ -166,7 +1068,10 @@ impl HungarianTable
                     "front, mid, rounded, voiced, open, X, state;" ),
             "ü" =>
                 Table::from_str(
-                    "front, mid, rounded, voiced, open, X, state;" ),
+                    "front, high, rounded, voiced, open, X, state;" ),
+            "ë" =>
+                Table::from_str(
+                    "front, mid, unrounded, voiced, open, X, state;" ),
             // long vowels
             "á" =>
                 Table::from_str(
@@ -189,6 +1094,19 @@ impl HungarianTable
             "ű" =>
                 Table::from_str(
                     "2 front, 2 high, 2 rounded, 2 voiced, 2 open, X X, 2 state;" ),
+            // dialectal long vowels and diphthongs
+            "å" =>
+                Table::from_str(
+                    "2 back, 2 low, 2 rounded, 2 voiced, 2 open, X X, 2 state;" ),
+            "ei" =>
+                Table::from_str(
+                    "2 front, mid-high high, 2 unrounded, 2 voiced, 2 open, X X, 2 state;" ),
+            "ou" =>
+                Table::from_str(
+                    "2 back, mid-high high, 2 rounded, 2 voiced, 2 open, X X, 2 state;" ),
+            "öü" =>
+                Table::from_str(
+                    "2 front, mid-high high, 2 rounded, 2 voiced, 2 open, X X, 2 state;" ),
             // stops
             "b" =>
                 Table::from_str(
@@ -207,10 +1125,10 @@ impl HungarianTable
                     "2 dentialveolar, 2 _, 2 _, 2 voiceless, 2 stop, 2 X, state event;" ),
             "c" =>
                 Table::from_str(
-                    "2 dentialveolar, 2 _, 2 _, 2 voiceless, 2 stop, 2 X, state event;" ),
+                    "2 dentialveolar, 2 _, 2 _, 2 voiceless, stop slit, 2 X, state event;" ),
             "č" =>
                 Table::from_str(
-                    "2 coronal, 2 _, 2 _, 2 voiceless, 2 stop, 2 X, state event;" ),
+                    "2 coronal, 2 _, 2 _, 2 voiceless, stop slit, 2 X, state event;" ),
             "ď" =>
                 Table::from_str(
                     "2 palatal, 2 _, 2 _, 2 voiced, 2 stop, 2 X, state event;" ),
@@ -229,10 +1147,10 @@ impl HungarianTable
                     "3 dentialveolar, 3 _, 3 _, 3 voiced, 3 stop , X 2 X, 2 state event;" ),
             "C" =>
                 Table::from_str(
-                    "3 dentialveolar, 3 _, 3 _, 3 voiceless, 3 stop, X 2 X, 2 state event;" ),
+                    "3 dentialveolar, 3 _, 3 _, 3 voiceless, 2 stop slit, X 2 X, 2 state event;" ),
             "Č" =>
                 Table::from_str(
-                    "3 coronal, 3 _, 3 _, 3 voiceless, 3 stop, X 2 X, 2 state event;" ),
+                    "3 coronal, 3 _, 3 _, 3 voiceless, 2 stop slit, X 2 X, 2 state event;" ),
             "Ď" =>
                 Table::from_str(
                     "3 palatal, 3 _, 3 _, 3 voiced, 3 stop, X 2 X, 2 state event;" ),
@@ -296,6 +1214,18 @@ impl HungarianTable
             "h" =>
                 Table::from_str(
                     "_, _, _, voiceless, approximate, X, state" ),
+            // velar fricative (the "h" reading of "ch")
+            "x" =>
+                Table::from_str(
+                    "velar, _, _, voiceless, slit, X, state;" ),
+            // /x/ before a voiced obstruent
+            "ɣ" =>
+                Table::from_str(
+                    "velar, _, _, voiced, slit, X, state;" ),
+            // voiced (intervocalic or assimilated) /h/
+            "ɦ" =>
+                Table::from_str(
+                    "_, _, _, voiced, approximate, X, state;" ),
             "l" =>
                 Table::from_str(
                     "dentialveolar, _, _, voiced, lateral, X, state;" ),
@@ -342,3 +1272,332 @@ impl HungarianTable
         }
     }
 }
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::tabular::grapheme::GraphemeTabular;
+
+    fn symbols( converter: &HungarianTable, word: &str ) -> Vec<String>
+    {
+        converter.transcribe( word ).unwrap().segments.into_iter().
+            map( | segment | segment.symbol ).collect()
+    }
+
+    fn table( symbols: &[&str] ) -> Table<'static>
+    {
+        let segments: Vec<(String, Range<usize>)> =
+            symbols.iter().map( | s | ( s.to_string(), 0..0 ) ).collect();
+        HungarianTable::from_segments( &segments ).unwrap()
+    }
+
+    #[test]
+    fn final_h_exceptions_ignore_case()
+    {
+        let mut converter = HungarianTable::new();
+        assert_eq!( symbols( &converter, "Sah" ), ["š", "a", "h"] );
+        assert_eq!( symbols( &converter, "düh" ), ["d", "ü"] );
+        converter.add_final_h_exception( "Düh" );
+        assert_eq!( symbols( &converter, "düh" ), ["d", "ü", "h"] );
+        assert_eq!( symbols( &converter, "DÜH" ), ["d", "ü", "h"] );
+    }
+
+    #[test]
+    fn geminate_h_is_long()
+    {
+        let converter = HungarianTable::new();
+        assert_eq!( symbols( &converter, "méhhez" ), ["m", "é", "H", "e", "z"] );
+    }
+
+    #[test]
+    fn from_string_applies_the_speech_style()
+    {
+        // careful speech by default, no optional rule applying:
+        let mut converter = HungarianTable::new();
+        assert_eq!( symbols( &converter, "balra" ), ["b", "a", "l", "r", "a"] );
+        assert_eq!( symbols( &converter, "elrejt" ), ["e", "l", "r", "e", "j", "t"] );
+        converter.set_speech_style( SpeechStyle::Normal );
+        assert_eq!( symbols( &converter, "balra" ), ["b", "a", "R", "a"] );
+    }
+
+    #[test]
+    fn variants_are_labelled_with_their_rules()
+    {
+        let mut converter = HungarianTable::new();
+        converter.set_speech_style( SpeechStyle::Casual );
+        let variants = converter.from_string_variants( "várja" ).unwrap();
+        let labels: Vec<Vec<OptionalRule>> =
+            variants.iter().map( | v | v.rules.clone() ).collect();
+        assert_eq!( labels,
+                    [vec![],
+                     vec![OptionalRule::JAssimilation],
+                     vec![OptionalRule::JAssimilation, OptionalRule::Degemination]] );
+        assert_eq!( variants[0].table, table( &["v", "á", "r", "j", "a"] ) );
+        assert_eq!( variants[1].table, table( &["v", "á", "R", "a"] ) );
+        assert_eq!( variants[2].table, converter.from_string( "várja" ).unwrap() );
+    }
+
+    #[test]
+    fn geminates_keep_the_boundary_between_their_halves()
+    {
+        let converter = HungarianTable::new();
+        let transcription = converter.transcribe( "kéz+zel" ).unwrap();
+        let spans: Vec<(String, Range<usize>)> =
+            transcription.segments.into_iter().
+            map( | segment | ( segment.symbol, segment.span ) ).collect();
+        assert_eq!( spans[2..5],
+                    [( "z".to_string(), 3..4 ), ( "+".to_string(), 4..5 ),
+                     ( "z".to_string(), 5..6 )] );
+        assert_eq!( converter.to_ipa( &transcription.table, IpaStyle::Broad ).unwrap(),
+                    "keːzːɛl" );
+        assert_eq!( symbols( &converter, "méh|hús" ),
+                    ["m", "é", "h", "|", "h", "ú", "š"] );
+    }
+
+    #[test]
+    fn readings_differing_in_boundaries_only_count_once()
+    {
+        let converter = HungarianTable::new();
+        let readings: Vec<String> =
+            converter.from_string_readings( "házsor", None ).unwrap().
+            into_iter().map( | r | r.segmentation ).collect();
+        assert_eq!( readings, ["házsor", "ház+sor"] );
+        let lexicon: Set<String> =
+            ["ház", "sor"].iter().map( | m | m.to_string() ).collect();
+        let readings: Vec<String> =
+            converter.from_string_readings( "házsor", Some( &lexicon ) ).unwrap().
+            into_iter().map( | r | r.segmentation ).collect();
+        assert_eq!( readings, ["ház+sor"] );
+        // kis+s+zék is pronounced as kiss+zék:
+        assert_eq!( converter.from_string_readings( "kisszék", None ).unwrap().len(), 3 );
+    }
+
+    #[test]
+    fn ch_is_a_velar_fricative_taking_part_in_voicing()
+    {
+        let mut converter = HungarianTable::new();
+        converter.set_orthography( Orthography::Loanwords );
+        assert_eq!( symbols( &converter, "Bach" ), ["b", "a", "x"] );
+        assert_eq!( symbols( &converter, "Bach+ból" ),
+                    ["b", "a", "ɣ", "+", "b", "ó", "l"] );
+        let table = converter.transcribe( "Bach+ból" ).unwrap().table;
+        assert_eq!( converter.to_ipa( &table, IpaStyle::Broad ).unwrap(), "bɒɣboːl" );
+        assert_eq!( converter.to_x_sampa( &table, IpaStyle::Broad ).unwrap(), "bQGbo:l" );
+        converter.set_ch_reading( ChReading::Cs );
+        assert_eq!( symbols( &converter, "Bach" ), ["b", "a", "č"] );
+    }
+
+    #[test]
+    fn ch_and_ph_are_two_letters_in_native_words()
+    {
+        let mut converter = HungarianTable::new();
+        assert_eq!( symbols( &converter, "kaphat" ), ["k", "a", "p", "h", "a", "t"] );
+        assert_eq!( symbols( &converter, "lophat" ), ["l", "o", "p", "h", "a", "t"] );
+        assert_eq!( symbols( &converter, "lánchíd" ),
+                    ["l", "á", "n", "c", "h", "í", "d"] );
+        // never across a boundary, even in loanwords:
+        converter.set_orthography( Orthography::Loanwords );
+        assert_eq!( symbols( &converter, "photo" ), ["f", "o", "t", "o"] );
+        assert_eq!( symbols( &converter, "kap+hat" ),
+                    ["k", "a", "p", "+", "h", "a", "t"] );
+    }
+
+    #[test]
+    fn names_use_their_orthography_and_exceptions()
+    {
+        let mut converter = HungarianTable::new();
+        converter.set_orthography( Orthography::Names );
+        assert_eq!( symbols( &converter, "Széchenyi" ),
+                    ["s", "é", "č", "e", "ń", "i"] );
+        assert_eq!( symbols( &converter, "Kossuth" ), ["k", "o", "š", "u", "t"] );
+        assert_eq!( symbols( &converter, "Dessewffy" ),
+                    ["d", "e", "ž", "ő", "f", "i"] );
+    }
+
+    #[test]
+    fn transcriptions_keep_case_and_punctuation()
+    {
+        let converter = HungarianTable::new();
+        let transcription = converter.transcribe( "Kossuth-díj!" ).unwrap();
+        let uppercase: Vec<bool> =
+            transcription.segments.iter().map( | s | s.uppercase ).collect();
+        assert_eq!( uppercase[..3], [true, false, false] );
+        assert!( uppercase[3..].iter().all( | u | ! u ) );
+        assert_eq!( transcription.punctuation, [(12, '!')] );
+        assert!( converter.transcribe( "ke\u{327}z" ).is_err() );
+    }
+
+    #[test]
+    fn phrase_words_keep_their_sandhi_halves()
+    {
+        let converter = HungarianTable::new();
+        let phrase = converter.from_phrase( "két gyerek" ).unwrap();
+        assert_eq!( phrase.words,
+                    [table( &["k", "é", "ď"] ), table( &["ď", "e", "r", "e", "k"] )] );
+        assert_eq!( converter.to_ipa( &phrase.table, IpaStyle::Broad ).unwrap(),
+                    "keːɟ ɟɛrɛk" );
+        assert!( converter.to_spellings( &phrase.table, None ).unwrap().
+                 contains( &"két gyerek".to_string() ) );
+    }
+
+    #[test]
+    fn transcriptions_trace_segments_and_columns_to_letters()
+    {
+        let converter = HungarianTable::new();
+        let transcription = converter.transcribe( "taxi" ).unwrap();
+        let spans: Vec<(String, Range<usize>)> =
+            transcription.segments.iter().
+            map( | segment | ( segment.symbol.clone(), segment.span.clone() ) ).collect();
+        assert_eq!( spans,
+                    [( "t".to_string(), 0..1 ), ( "a".to_string(), 1..2 ),
+                     ( "k".to_string(), 2..3 ), ( "s".to_string(), 2..3 ),
+                     ( "i".to_string(), 3..4 )] );
+        let transcription = converter.transcribe( "hosszú" ).unwrap();
+        assert_eq!( transcription.column_spans.len(),
+                    transcription.table.number_of_columns() );
+        // the geminate sz spans the three letters of "ssz":
+        assert_eq!( transcription.column_spans[2..4], [2..5, 2..5] );
+    }
+
+    #[test]
+    fn spellings_are_ranked_together()
+    {
+        let converter = HungarianTable::new();
+        let table = converter.from_string( "kézből" ).unwrap();
+        assert_eq!( converter.to_spellings( &table, None ).unwrap()[0], "kézből" );
+        let table = converter.from_string( "hej" ).unwrap();
+        assert_eq!( converter.to_spellings( &table, None ).unwrap(), ["hej", "hely"] );
+        let frequencies: Map<String, usize> =
+            vec![( "hely".to_string(), 10 )].into_iter().collect();
+        assert_eq!( converter.to_spellings( &table, Some( &frequencies ) ).unwrap(),
+                    ["hely", "hej"] );
+    }
+
+    #[test]
+    fn western_l_deletion_is_limited_to_preverbs_and_volt()
+    {
+        let converter = HungarianTable::with_dialect( Dialect::Western );
+        assert_eq!( symbols( &converter, "volt" ), ["v", "ou", "t"] );
+        assert_eq!( symbols( &converter, "el|ment" ), ["ei", "|", "m", "e", "n", "t"] );
+        assert_eq!( symbols( &converter, "elvtárs" ), ["e", "l", "f", "t", "á", "r", "š"] );
+        assert_eq!( symbols( &converter, "első" ), ["e", "l", "š", "öü"] );
+        assert_eq!( symbols( &converter, "alma" ), ["a", "l", "m", "a"] );
+        assert_eq!( symbols( &converter, "film" ), ["f", "i", "l", "m"] );
+    }
+
+    #[test]
+    fn prosodic_tiers_follow_the_converted_ones()
+    {
+        let converter = HungarianTable::new();
+        let table = converter.from_string( "alma" ).unwrap();
+        assert_eq!( table.tiers.len(), 8 );
+        assert_eq!( converter.tier_name_to_tier_index( &"boundary" ), 7 );
+        let syllabified = converter.syllabify( &table ).unwrap();
+        assert_eq!( syllabified.tiers.len(), 9 );
+        assert_eq!( converter.tier_index_to_tier_name( 8 ), "syllable" );
+        let stressed = converter.stress( &table, &[false] ).unwrap();
+        assert_eq!( stressed.tiers.len(), 10 );
+        assert_eq!( converter.tier_index_to_tier_name( 9 ), "stress" );
+        assert_eq!( converter.syllables( &table ).unwrap(), [0..2, 2..4] );
+    }
+
+    #[test]
+    fn suffixes_are_attached_to_the_stem_table()
+    {
+        let converter = HungarianTable::new();
+        let attach = | word: &'static str, template: &str |
+            converter.attach_suffix( &converter.from_string( word ).unwrap(), template ).
+            unwrap();
+        // the result is the table of the suffixed word as spelled:
+        for ( word, template, spelled ) in [
+            ( "alma", "-bAn", "almá+ban" ),
+            ( "kéz", "-vAl", "kéz+zel" ),
+            ( "toll", "-vAl", "tol+lal" ),
+            ( "kert", "-vAl", "kert+el" ),
+            ( "kút", "-tÓl", "kút+tól" ),
+            ( "kert", "-tÓl", "kert+ől" ),
+            ( "ott", "-rÓl", "ot+ról" ),
+            ( "kút", "-bA", "kúd+ba" ),
+            ( "kéz", "-tÓl", "kész+től" ),
+        ]
+        {
+            assert_eq!( attach( word, template ), converter.from_string( spelled ).unwrap(),
+                        "{}{}", word, template );
+        }
+        assert_eq!( attach( "kéz", "-vAl" ), table( &["k", "é", "z", "+", "z", "e", "l"] ) );
+        // the columns before the seam are kept, tiers added dropped:
+        let stressed = converter.stress( &converter.from_string( "szék" ).unwrap(), &[false] ).
+            unwrap();
+        assert_eq!( converter.attach_suffix( &stressed, "-hOz" ).unwrap(),
+                    table( &["s", "é", "k", "+", "h", "e", "z"] ) );
+    }
+
+    #[test]
+    fn natural_classes_are_the_segments_subsumed()
+    {
+        let converter = HungarianTable::new();
+        assert_eq!( converter.natural_class( "voiced + stop" ).unwrap(),
+                    ["b", "d", "g", "ď", "B", "D", "Ď", "G"] );
+        assert_eq!( converter.natural_class( "rounded, time=X" ).unwrap(),
+                    ["a", "o", "u", "ö", "ü"] );
+        // the stop of an affricate is part of a contour:
+        assert!( ! converter.natural_class( "stop" ).unwrap().contains( &"c" ) );
+        assert!( ! converter.natural_class( "slit" ).unwrap().contains( &"c" ) );
+        assert!( converter.natural_class( "syllable=onset" ).is_err() );
+        assert!( converter.natural_class( "nasal, lateral" ).is_err() );
+    }
+
+    #[test]
+    fn class_specifications_cover_no_other_segment()
+    {
+        let converter = HungarianTable::new();
+        assert_eq!( converter.class_specification( &["ö", "ü"] ).unwrap().to_string(),
+                    "tongue=front, lips=rounded, voice=voiced, airflow=open, \
+                     eventuality=state, time=X" );
+        let spec = converter.class_specification( &["b", "d", "g", "ď"] ).unwrap();
+        assert_eq!( converter.natural_class( &spec.to_string() ).unwrap(),
+                    ["b", "d", "g", "ď"] );
+        assert_eq!( converter.class_specification( &["b", "d", "g"] ).unwrap_err(),
+                    "Not a natural class: voice=voiced, airflow=stop, time=X also covers ď" );
+        assert!( converter.class_specification( &["q"] ).is_err() );
+        // contours are not specified:
+        assert!( converter.class_specification( &["c", "č"] ).is_err() );
+    }
+
+    #[test]
+    fn feature_charts_list_the_inventory()
+    {
+        let converter = HungarianTable::new();
+        let chart = converter.feature_chart( ChartFormat::Csv ).unwrap();
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!( lines.len(), inventory::SEGMENTS.len() + 1 );
+        assert_eq!( lines[0], "segment,tongue,jaw,lips,voice,airflow,time,eventuality" );
+        assert_eq!( lines[1], "a,back,low,rounded,voiced,open,X,state" );
+        assert!( lines.contains( &"c,dentialveolar,,,voiceless,stop slit,X,state event" ) );
+        let binary = converter.binary_feature_chart( ChartFormat::Tsv ).unwrap();
+        assert!( binary.lines().next().unwrap().ends_with( "\teventuality=event" ) );
+        assert_eq!( binary.lines().count(), inventory::SEGMENTS.len() + 1 );
+    }
+
+    #[test]
+    fn single_words_reject_whitespace()
+    {
+        let converter = HungarianTable::new();
+        assert!( converter.from_string( "két gyerek" ).is_err() );
+        assert_eq!( converter.from_phrase( "két gyerek" ).unwrap().words.len(), 2 );
+    }
+
+    #[test]
+    fn spelled_out_numerals_keep_their_parts_apart()
+    {
+        let converter = HungarianTable::new();
+        let ipa = | text: &str |
+            converter.to_ipa( &converter.from_text( text ).unwrap().table,
+                              IpaStyle::Broad ).unwrap();
+        // nyolc+száz, not nyol+csz...:
+        assert_eq!( ipa( "1848-ban voltunk." ),
+                    "ɛzɛrɲolt͡ssaːznɛɟvɛnɲold͡zbɒn voltunk" );
+        assert_eq!( ipa( "900" ), "kilɛnt͡ssaːz" );
+    }
+}
@@ -0,0 +1,100 @@
+// The segments in the notation of `HungarianTable::from_grapheme`
+// and their classes, shared by the natural classes, the
+// decomposition of tables, the suffix seam and the spelling of
+// segment sequences.
+
+/// The segments that `from_grapheme` has tables for.
+pub const SEGMENTS: [&str; 68] = [
+    "a", "e", "i", "o", "u", "ö", "ü", "ë",
+    "á", "é", "í", "ó", "ú", "ő", "ű", "å", "ei", "ou", "öü",
+    "b", "d", "g", "p", "t", "c", "č", "ď", "ť", "k",
+    "B", "D", "C", "Č", "Ď", "Ť", "G", "P", "T", "K",
+    "v", "f", "z", "s", "ž", "š", "V", "F", "Z", "S", "Ž", "Š",
+    "j", "h", "x", "ɣ", "ɦ", "l", "H", "J", "L", "m", "n", "ń", "M", "N", "Ń",
+    "r", "R",
+];
+
+/// The boundaries: morpheme, compound and word.
+pub const BOUNDARIES: [&str; 3] = ["+", "|", "#"];
+
+/// The vowels, the dialectal ones included.
+pub const VOWELS: [&str; 19] = [
+    "a", "e", "ë", "i", "o", "u", "ö", "ü",
+    "á", "é", "í", "ó", "ú", "ő", "ű", "å", "ei", "ou", "öü",
+];
+
+/// Voiced obstruents and their voiceless counterparts.
+pub const VOICE_PAIRS: [(&str, &str); 16] = [
+    ("b", "p"), ("d", "t"), ("g", "k"), ("ď", "ť"), ("z", "s"), ("ž", "š"),
+    ("v", "f"), ("ɣ", "x"), ("ɦ", "h"),
+    ("B", "P"), ("D", "T"), ("G", "K"), ("Ď", "Ť"), ("Z", "S"), ("Ž", "Š"),
+    ("V", "F"),
+];
+
+/// Voiceless obstruents without a voiced counterpart.
+pub const VOICELESS: [&str; 4] = ["c", "č", "C", "Č"];
+
+// Obstruents undergoing voice assimilation without triggering it:
+const NON_TRIGGERS: [&str; 4] = ["v", "V", "h", "ɦ"];
+
+/// The segments and the boundaries.
+pub fn symbols() -> impl Iterator<Item = &'static str>
+{
+    SEGMENTS.iter().chain( BOUNDARIES.iter() ).copied()
+}
+
+pub fn is_vowel( segment: &str ) -> bool
+{
+    VOWELS.contains( &segment )
+}
+
+/// Whether `segment` is voiced, for obstruents.
+pub fn voicing( segment: &str ) -> Option<bool>
+{
+    if VOICE_PAIRS.iter().any( | (voiced, _) | *voiced == segment ) { Some( true ) }
+    else if VOICE_PAIRS.iter().any( | (_, voiceless) | *voiceless == segment ) ||
+        VOICELESS.contains( &segment ) { Some( false ) }
+    else { None }
+}
+
+/// Whether `segment` is an obstruent triggering voice assimilation.
+pub fn triggers_voicing( segment: &str ) -> bool
+{
+    voicing( segment ).is_some() && ! NON_TRIGGERS.contains( &segment )
+}
+
+/// The obstruent `segment` with voicing `voiced`, if it has one.
+pub fn with_voicing( segment: &str, voiced: bool ) -> Option<&'static str>
+{
+    VOICE_PAIRS.iter().
+        find( | (v, f) | *v == segment || *f == segment ).
+        map( | (v, f) | if voiced { *v } else { *f } )
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::{triggers_voicing, voicing, with_voicing};
+
+    #[test]
+    fn obstruents_have_a_voicing()
+    {
+        assert_eq!( voicing( "z" ), Some( true ) );
+        assert_eq!( voicing( "x" ), Some( false ) );
+        assert_eq!( voicing( "č" ), Some( false ) );
+        assert_eq!( voicing( "n" ), None );
+        assert_eq!( with_voicing( "x", true ), Some( "ɣ" ) );
+        assert_eq!( with_voicing( "h", true ), Some( "ɦ" ) );
+        assert_eq!( with_voicing( "D", false ), Some( "T" ) );
+        assert_eq!( with_voicing( "c", true ), None );
+    }
+
+    #[test]
+    fn h_and_v_do_not_trigger_voicing()
+    {
+        assert!( triggers_voicing( "x" ) );
+        assert!( triggers_voicing( "f" ) );
+        assert!( ! triggers_voicing( "h" ) );
+        assert!( ! triggers_voicing( "v" ) );
+    }
+}
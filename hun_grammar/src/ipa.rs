@@ -0,0 +1,359 @@
+use std::ops::Range;
+
+use crate::tabular::table::Table;
+
+// IPA transcription of tables.  Segments are the column ranges
+// delimited by points where all tiers are aligned; each segment
+// is transcribed from the elements on its tiers (not from the
+// grapheme it has been converted from), so tables changed by
+// rules are transcribed as well.  Two or more slots on the time
+// tier make a segment long (`ː`), a stop-slit contour on the
+// airflow tier makes it an affricate (`t͡s`).
+
+/// The level of detail of IPA transcriptions.
+#[derive( PartialEq, Eq, Clone, Copy, Debug )]
+pub enum IpaStyle
+{
+    /// Phonemic transcription (ɒ, ɛ, v, t).
+    Broad,
+    /// Phonetic detail: dental place of articulation (t̪, n̪),
+    /// the approximant [ʋ].
+    Narrow,
+}
+
+/// The elements of a segment, tier by tier: a contour
+/// has several elements on a tier.
+pub struct Features<'a>
+{
+    pub tongue: Vec<&'a str>,
+    pub jaw: Vec<&'a str>,
+    pub lips: Vec<&'a str>,
+    pub voice: Vec<&'a str>,
+    pub airflow: Vec<&'a str>,
+    pub timing_slots: usize,
+    pub boundary: Vec<&'a str>,
+}
+
+// Vowels: tongue, jaw, lips -> broad IPA
+const VOWELS: [(&str, &str, &str, &str); 15] = [
+    ("back", "low", "rounded", "ɒ"),
+    ("back", "extra-low", "unrounded", "a"),
+    ("front", "low", "unrounded", "ɛ"),
+    ("front", "mid-high", "unrounded", "e"),
+    ("front", "high", "unrounded", "i"),
+    ("back", "mid", "rounded", "o"),
+    ("back", "mid-high", "rounded", "o"),
+    ("back", "high", "rounded", "u"),
+    ("front", "mid", "rounded", "ø"),
+    ("front", "mid-high", "rounded", "ø"),
+    ("front", "high", "rounded", "y"),
+    ("back", "high", "unrounded", "ɯ"),
+    ("back", "mid", "unrounded", "ɤ"),
+    ("front", "mid", "unrounded", "e"),
+    ("front", "extra-low", "unrounded", "æ"),
+];
+
+// Consonants: place (tongue or lips element, "glottal" if none),
+// manner, voiced -> IPA
+const CONSONANTS: [(&str, &str, bool, &str); 37] = [
+    ("lips", "stop", false, "p"), ("lips", "stop", true, "b"),
+    ("dentialveolar", "stop", false, "t"), ("dentialveolar", "stop", true, "d"),
+    ("palatal", "stop", false, "c"), ("palatal", "stop", true, "ɟ"),
+    ("velar", "stop", false, "k"), ("velar", "stop", true, "g"),
+    ("dentialveolar", "affricate", false, "t͡s"),
+    ("dentialveolar", "affricate", true, "d͡z"),
+    ("coronal", "affricate", false, "t͡ʃ"),
+    ("coronal", "affricate", true, "d͡ʒ"),
+    ("lower-to-teeth", "slit", false, "f"), ("lower-to-teeth", "slit", true, "v"),
+    ("dentialveolar", "slit", false, "s"), ("dentialveolar", "slit", true, "z"),
+    ("coronal", "slit", false, "ʃ"), ("coronal", "slit", true, "ʒ"),
+    ("palatal", "slit", false, "ç"), ("palatal", "slit", true, "ʝ"),
+    ("velar", "slit", false, "x"), ("velar", "slit", true, "ɣ"),
+    ("lower-to-teeth", "approximate", false, "f"),
+    ("lower-to-teeth", "approximate", true, "v"),
+    ("palatal", "approximate", false, "ç"), ("palatal", "approximate", true, "j"),
+    ("glottal", "approximate", false, "h"), ("glottal", "approximate", true, "ɦ"),
+    ("back", "approximate", true, "u\u{32f}"),
+    ("dentialveolar", "lateral", true, "l"), ("palatal", "lateral", true, "ʎ"),
+    ("closed", "nasal", true, "m"), ("lips", "nasal", true, "m"),
+    ("dentialveolar", "nasal", true, "n"), ("palatal", "nasal", true, "ɲ"),
+    ("velar", "nasal", true, "ŋ"),
+    ("dentialveolar", "trill", true, "r"),
+];
+
+/// The column ranges of the segments of `table`: a segment ends
+/// where the slots of all tiers end.
+pub fn segments( table: &Table ) -> Vec<Range<usize>>
+{
+    let mut result = Vec::new();
+    let mut start = 0;
+    for column in 0..table.number_of_columns()
+    {
+        if table.tiers.iter().all( | tier | tier.slots[column].is_right_aligned() )
+        {
+            result.push( start..column + 1 );
+            start = column + 1;
+        }
+    }
+    if start < table.number_of_columns()
+    {
+        result.push( start..table.number_of_columns() );
+    }
+    result
+}
+
+// The elements of a tier in the columns `columns`, a slot
+// spanning several columns counted once:
+fn elements<'a>( table: &Table<'a>, tier: Option<usize>, columns: &Range<usize> )
+    -> Vec<&'a str>
+{
+    let mut result = Vec::new();
+    if let Some( tier ) = tier
+    {
+        for column in columns.clone()
+        {
+            let slot = &table.tiers[tier].slots[column];
+            if column == columns.start || slot.is_left_aligned()
+            {
+                if let Some( element ) = slot.element()
+                {
+                    result.push( element );
+                }
+            }
+        }
+    }
+    result
+}
+
+/// The features of the segment in the columns `columns` of `table`,
+/// whose tiers are named `tier_names`.
+pub fn features<'a>(
+    table: &Table<'a>, tier_names: &[&str], columns: &Range<usize> )
+    -> Features<'a>
+{
+    let tier = | name: &str | tier_names.iter().position( | n | *n == name );
+    Features
+    {
+        tongue: elements( table, tier( "tongue" ), columns ),
+        jaw: elements( table, tier( "jaw" ), columns ),
+        lips: elements( table, tier( "lips" ), columns ),
+        voice: elements( table, tier( "voice" ), columns ),
+        airflow: elements( table, tier( "airflow" ), columns ),
+        timing_slots: elements( table, tier( "time" ), columns ).len(),
+        boundary: elements( table, tier( "boundary" ), columns ),
+    }
+}
+
+// The element of a contour for the `part`th part of a segment,
+// a single element extending over all parts:
+fn part_of<'a>( tier: &[&'a str], part: usize ) -> &'a str
+{
+    tier.get( part ).or( tier.last() ).copied().unwrap_or( "" )
+}
+
+fn narrow( symbol: &str, place: &str ) -> String
+{
+    match ( symbol, place )
+    {
+        ( "v", "lower-to-teeth" ) => "ʋ".to_string(),
+        ( "t", _ ) | ( "d", _ ) | ( "n", _ ) | ( "l", _ ) =>
+            format!( "{}\u{32a}", symbol ),
+        ( "t͡s", _ ) => "t̪͡s".to_string(),
+        ( "d͡z", _ ) => "d̪͡z".to_string(),
+        _ => symbol.to_string()
+    }
+}
+
+// The IPA symbol of a vowel or diphthong:
+fn vowel_to_ipa( features: &Features ) -> Result<String, String>
+{
+    // a diphthong has a contour on the tongue, jaw or lips tier:
+    let parts = [&features.tongue, &features.jaw, &features.lips].iter().
+        map( | tier | tier.len() ).max().unwrap_or( 0 ).max( 1 );
+    let mut vowels = Vec::new();
+    for part in 0..parts
+    {
+        let ( tongue, jaw, lips ) =
+            ( part_of( &features.tongue, part ),
+              part_of( &features.jaw, part ),
+              part_of( &features.lips, part ) );
+        match VOWELS.iter().
+            find( | (t, j, l, _) | *t == tongue && *j == jaw && *l == lips )
+        {
+            Some( (_, _, _, ipa) ) => vowels.push( ipa.to_string() ),
+            None =>
+            {
+                return Err( format!( "No IPA vowel for {} {} {}",
+                                     tongue, jaw, lips ) );
+            }
+        }
+    }
+    if parts > 1
+    {
+        // the non-syllabic mark on the glide: the last part of a
+        // closing diphthong (ei̯), the first one otherwise (i̯e):
+        let last = parts - 1;
+        if part_of( &features.jaw, last ) == "high" &&
+            part_of( &features.jaw, 0 ) != "high"
+        {
+            return Ok( format!( "{}{}\u{32f}", vowels[..last].concat(),
+                                vowels[last] ) );
+        }
+        return Ok( format!( "{}\u{32f}{}", vowels[0], vowels[1..].concat() ) );
+    }
+    Ok( vowels.concat() )
+}
+
+// The IPA symbol of a consonant:
+fn consonant_to_ipa( features: &Features, voiced: bool, style: IpaStyle )
+    -> Result<String, String>
+{
+    let place =
+        match ( features.tongue.first(), features.lips.first() )
+        {
+            ( Some( tongue ), _ ) => *tongue,
+            ( None, Some( lips ) ) => *lips,
+            ( None, None ) => "glottal"
+        };
+    let manner =
+        match features.airflow.as_slice()
+        {
+            ["stop", "slit"] => "affricate",
+            [first, ..] => *first,
+            [] => { return Err( "No airflow for segment".to_string() ); }
+        };
+    match CONSONANTS.iter().
+        find( | (p, m, v, _) | *p == place && *m == manner && *v == voiced )
+    {
+        Some( (_, _, _, ipa) ) =>
+            match style
+            {
+                IpaStyle::Broad => Ok( ipa.to_string() ),
+                IpaStyle::Narrow => Ok( narrow( ipa, place ) ),
+            },
+        None =>
+            Err( format!( "No IPA consonant for {} {} {}",
+                          place, manner,
+                          if voiced { "voiced" } else { "voiceless" } ) )
+    }
+}
+
+/// The IPA symbol of a segment (empty for a morpheme or compound
+/// boundary, a space for a word boundary).
+pub fn segment_to_ipa( features: &Features, style: IpaStyle )
+    -> Result<String, String>
+{
+    if let Some( boundary ) = features.boundary.first()
+    {
+        return Ok( if *boundary == "word" { " " } else { "" }.to_string() );
+    }
+    let voiced = features.voice.contains( &"voiced" );
+    let symbol =
+        if features.airflow == ["open"]
+        {
+            vowel_to_ipa( features )?
+        }
+        else if features.jaw.contains( &"syllabic" )
+        {
+            // Slovak syllabic liquids (vlk, vŕba):
+            format!( "{}\u{329}", consonant_to_ipa( features, voiced, style )? )
+        }
+        else
+        {
+            consonant_to_ipa( features, voiced, style )?
+        };
+    if features.timing_slots > 1
+    {
+        Ok( format!( "{}ː", symbol ) )
+    }
+    else { Ok( symbol ) }
+}
+
+// Whether a segment is a short voiced [d]:
+fn voiced_dental_stop( features: &Features ) -> bool
+{
+    features.airflow == ["stop"] && features.tongue == ["dentialveolar"] &&
+        features.voice == ["voiced"] && features.timing_slots == 1
+}
+
+/// The IPA transcription of `table`, whose tiers are named `tier_names`.
+/// The halves of a geminate across a morpheme or compound boundary
+/// (kéz+zel) are transcribed as a long consonant.  A [d] directly
+/// followed by a voiced sibilant is a voiced affricate, written with
+/// a tie bar (edz, bridzs, and the voiced c and cs of malacból,
+/// Pécsben); a boundary between them keeps them apart (ad+zár).
+pub fn table_to_ipa( table: &Table, tier_names: &[&str], style: IpaStyle )
+    -> Result<String, String>
+{
+    let mut result = String::new();
+    // the last consonant, while only such boundaries follow it:
+    let mut previous: Option<String> = None;
+    // whether the last segment is a [d], no boundary following it:
+    let mut stop = false;
+    for columns in segments( table )
+    {
+        let features = features( table, tier_names, &columns );
+        let symbol = segment_to_ipa( &features, style )?;
+        if symbol.is_empty()
+        {
+            stop = false;
+            continue;
+        }
+        if previous.as_ref() == Some( &symbol ) && ! symbol.ends_with( 'ː' )
+        {
+            result.push( 'ː' );
+            previous = None;
+            stop = false;
+            continue;
+        }
+        if stop && features.airflow == ["slit"] && features.voice == ["voiced"] &&
+            ( features.tongue == ["dentialveolar"] || features.tongue == ["coronal"] )
+        {
+            result.push( '\u{361}' );
+        }
+        stop = voiced_dental_stop( &features );
+        previous =
+            if features.airflow.is_empty() || features.airflow == ["open"] { None }
+            else { Some( symbol.clone() ) };
+        result.push_str( &symbol );
+    }
+    Ok( result )
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::IpaStyle;
+    use crate::hungarian_grapheme::HungarianTable;
+    use crate::tabular::grapheme::GraphemeTabular;
+
+    fn ipa( word: &'static str ) -> String
+    {
+        let converter = HungarianTable::new();
+        let table = converter.from_string( word ).unwrap();
+        converter.to_ipa( &table, IpaStyle::Broad ).unwrap()
+    }
+
+    #[test]
+    fn vowels_and_affricates_have_their_own_symbols()
+    {
+        assert_eq!( ipa( "üveg" ), "yvɛg" );
+        assert_eq!( ipa( "öröm" ), "ørøm" );
+        assert_eq!( ipa( "cica" ), "t͡sit͡sɒ" );
+        assert_eq!( ipa( "csata" ), "t͡ʃɒtɒ" );
+        assert_eq!( ipa( "tata" ), "tɒtɒ" );
+    }
+
+    #[test]
+    fn voiced_affricates_have_a_tie_bar()
+    {
+        assert_eq!( ipa( "edz" ), "ɛd͡z" );
+        assert_eq!( ipa( "bridzs" ), "brid͡ʒ" );
+        assert_eq!( ipa( "malacból" ), "mɒlɒd͡zboːl" );
+        assert_eq!( ipa( "Pécsben" ), "peːd͡ʒbɛn" );
+        assert_eq!( ipa( "ad+zár" ), "ɒdzaːr" );
+        let converter = HungarianTable::new();
+        let table = converter.from_string( "edz" ).unwrap();
+        assert_eq!( converter.to_ipa( &table, IpaStyle::Narrow ).unwrap(), "ɛd̪͡z" );
+    }
+}
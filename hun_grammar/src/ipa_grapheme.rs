@@ -0,0 +1,300 @@
+use crate::tabular::grapheme::GraphemeTabular;
+use crate::tabular::tabular::Tabular;
+use crate::tabular::table::Table;
+
+use crate::hungarian_grapheme::HungarianTable;
+use crate::ipa::IpaStyle;
+use crate::inventory;
+use crate::x_sampa;
+
+/**
+A converter from IPA transcriptions, implementing the
+`tabular::grapheme::GraphemeTabular` trait over the tiers of
+`HungarianTable`: the segments read are given the tables of the
+corresponding Hungarian segments, so equivalent transcriptions
+(broad or narrow, `t͡s`, `t͜s` or `ʦ`, `tː` or `tt`) give the same
+table as each other and as the Hungarian spelling.
+The voiced affricates `d͡z` and `d͡ʒ` (or `ʣ`, `ʤ`) are read as [d]
+followed by [z] or [ʒ], as `HungarianTable` represents dz and dzs.
+Stress and syllable marks and diacritics not distinctive in
+Hungarian (aspiration, dental, syllabic, nasalized, unreleased)
+are ignored; a space is a word boundary, `+` and `|` are morpheme
+and compound boundaries as in `HungarianTable`.
+*/
+pub struct IpaTable
+{
+    hungarian: HungarianTable,
+    // IPA strings and the Hungarian segments they stand for,
+    // longer strings first:
+    symbols: Vec<(String, String)>,
+}
+
+// Alternative notations and their form in `symbols`:
+const ALIASES: [(&str, &str); 12] = [
+    ("\u{35c}", "\u{361}"),      // undertie for tie bar
+    (":", "ː"), ("ɡ", "g"), ("ɑ", "ɒ"),
+    ("ʦ", "t͡s"), ("ʧ", "t͡ʃ"),
+    ("tt͡s", "t͡sː"), ("tt͡ʃ", "t͡ʃː"),
+    ("b̥", "p"), ("d̥", "t"), ("z̥", "s"), ("ʒ̥", "ʃ"),
+];
+
+// Voiced affricate ligatures and the segments they stand for:
+const LIGATURES: [(char, &str); 2] = [('ʣ', "z"), ('ʤ', "ž")];
+
+// The length of the longest IPA string read as a segment (tt͡ʃ):
+const MAX_SYMBOL_LENGTH: usize = 4;
+
+// Marks carrying no distinction in the tables:
+const IGNORED: [char; 11] = [
+    'ˈ', 'ˌ', '.', 'ʰ', '\u{32a}', '\u{329}', '\u{30d}', '\u{303}',
+    '\u{31a}', '\u{32f}', 'ˑ',
+];
+
+impl GraphemeTabular<Table<'static>> for IpaTable
+{
+    type TierName = &'static str;
+
+    fn new() -> Self
+    {
+        let hungarian = HungarianTable::new();
+        let mut symbols: Vec<(String, String)> = Vec::new();
+        for symbol in inventory::SEGMENTS.iter()
+        {
+            let segment = vec![symbol.to_string()];
+            for style in [IpaStyle::Broad, IpaStyle::Narrow].iter()
+            {
+                if let Ok( ipa ) = hungarian.segments_to_ipa( &segment, *style )
+                {
+                    let ipa: String =
+                        ipa.chars().filter( | c | ! IGNORED.contains( c ) ).collect();
+                    if ! symbols.iter().any( | (i, _) | *i == ipa )
+                    {
+                        symbols.push( ( ipa, symbol.to_string() ) );
+                    }
+                }
+            }
+        }
+        symbols.sort_by_key( | (ipa, _) | std::cmp::Reverse( ipa.len() ) );
+        IpaTable { hungarian, symbols }
+    }
+
+    fn tier_name_to_tier_index(
+        &self, tier_name: &Self::TierName )
+        -> usize
+    {
+        self.hungarian.tier_name_to_tier_index( tier_name )
+    }
+    fn tier_index_to_tier_name(
+        &self, tier_index: usize )
+        -> Self::TierName
+    {
+        self.hungarian.tier_index_to_tier_name( tier_index )
+    }
+    fn from_string( &self, graphemes: &'static str )
+                         -> Result<Table<'static>, String>
+    {
+        self.from_ipa( graphemes )
+    }
+}
+
+impl IpaTable
+{
+    /// The Hungarian segments (in the notation of
+    /// `HungarianTable::from_grapheme`) of an IPA transcription,
+    /// with the byte offset in `ipa` where each of them starts.
+    pub fn read_segments( &self, ipa: &str ) -> Result<Vec<(usize, String)>, String>
+    {
+        self.read( ipa ).map_err( | position |
+            format!( "Unknown IPA symbol \"{}\" at position {}",
+                     ipa[position..].chars().next().unwrap_or( ' ' ), position ) )
+    }
+
+    // `read_segments`, failing with the position of an unknown symbol:
+    fn read( &self, ipa: &str ) -> Result<Vec<(usize, String)>, usize>
+    {
+        let mut result: Vec<(usize, String)> = Vec::new();
+        let mut position = 0;
+        while position < ipa.len()
+        {
+            let rest = &ipa[position..];
+            let c = rest.chars().next().unwrap();
+            if IGNORED.contains( &c )
+            {
+                position += c.len_utf8();
+                continue;
+            }
+            // the tie bar of a voiced affricate (d͡z, d͡ʒ):
+            if ( c == '\u{361}' || c == '\u{35c}' ) &&
+                result.last().map( | (_, s) | s.as_str() ) == Some( "d" )
+            {
+                position += c.len_utf8();
+                continue;
+            }
+            if let Some( (_, slit) ) = LIGATURES.iter().find( | (l, _) | *l == c )
+            {
+                result.push( ( position, "d".to_string() ) );
+                result.push( ( position, slit.to_string() ) );
+                position += c.len_utf8();
+                continue;
+            }
+            let ( symbol, length ) =
+                if c.is_whitespace() { ( "#".to_string(), c.len_utf8() ) }
+                else if c == '+' || c == '|' { ( c.to_string(), 1 ) }
+                else
+                {
+                    self.longest_match( rest ).ok_or( position )?
+                };
+            match result.last()
+            {
+                // a doubled consonant is a geminate:
+                Some( (_, previous) )
+                    if *previous == symbol && symbol.to_uppercase() != symbol &&
+                    HungarianTable::from_grapheme( &symbol.to_uppercase() ).is_ok() =>
+                {
+                    let last = result.len() - 1;
+                    result[last].1 = symbol.to_uppercase();
+                }
+                Some( (_, previous) ) if symbol == "#" && previous == "#" => (),
+                _ => result.push( ( position, symbol ) )
+            }
+            position += length;
+        }
+        Ok( result )
+    }
+
+    // The Hungarian segment of the longest IPA symbol (with the
+    // alternative notations) that `s` starts with, and its length in `s`:
+    fn longest_match( &self, s: &str ) -> Option<(String, usize)>
+    {
+        let mut best: Option<(String, usize)> = None;
+        let mut candidates: Vec<(String, usize)> = Vec::new();
+        // prefixes of `s` (up to the longest symbol, ignored marks
+        // skipped) with alternative notations replaced:
+        let mut prefix = String::new();
+        for ( i, c ) in s.char_indices()
+        {
+            if IGNORED.contains( &c )
+            {
+                continue;
+            }
+            if prefix.chars().count() == MAX_SYMBOL_LENGTH
+            {
+                break;
+            }
+            prefix.push( c );
+            let mut normalized = prefix.clone();
+            for ( alias, canonical ) in ALIASES.iter()
+            {
+                normalized = normalized.replace( alias, canonical );
+            }
+            candidates.push( ( normalized, i + c.len_utf8() ) );
+        }
+        for ( normalized, length ) in candidates
+        {
+            if let Some( (_, symbol) ) =
+                self.symbols.iter().find( | (ipa, _) | *ipa == normalized )
+            {
+                best = Some( ( symbol.clone(), length ) );
+            }
+        }
+        best
+    }
+
+    /// The table of an IPA transcription.
+    pub fn from_ipa( &self, ipa: &str ) -> Result<Table<'static>, String>
+    {
+        self.segments_table( &self.read_segments( ipa )? )
+    }
+
+    /// The table of an X-SAMPA transcription.
+    pub fn from_x_sampa( &self, x_sampa: &str ) -> Result<Table<'static>, String>
+    {
+        let ( ipa, offsets ) = x_sampa::x_sampa_to_ipa( x_sampa )?;
+        match self.read( &ipa )
+        {
+            Ok( segments ) => self.segments_table( &segments ),
+            Err( position ) =>
+            {
+                // the character of `ipa` at `position`:
+                let index = ipa[..position].chars().count();
+                let start = offsets[index];
+                let end = offsets.iter().find( | o | **o > start ).
+                    copied().unwrap_or( x_sampa.len() );
+                Err( format!( "Unknown X-SAMPA symbol \"{}\" at position {}",
+                              &x_sampa[start..end], start ) )
+            }
+        }
+    }
+
+    fn segments_table( &self, segments: &[(usize, String)] )
+        -> Result<Table<'static>, String>
+    {
+        let mut result: Option<Table<'static>> = None;
+        for ( _, segment ) in segments
+        {
+            let table = HungarianTable::from_grapheme( segment )?;
+            result = Some( match result
+                           {
+                               Some( previous ) =>
+                                   Tabular::concatenate( &previous, &table ),
+                               None => table
+                           } );
+        }
+        result.ok_or_else( || "Empty IPA transcription".to_string() )
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::IpaTable;
+    use crate::tabular::grapheme::GraphemeTabular;
+    use crate::hungarian_grapheme::HungarianTable;
+    use crate::ipa::IpaStyle;
+
+    #[test]
+    fn notations_give_the_spelling_table()
+    {
+        let hungarian = HungarianTable::new();
+        let reader = IpaTable::new();
+        let cica = hungarian.from_string( "cica" ).unwrap();
+        for ipa in ["t͡sit͡sɒ", "t͜sit͜sɑ", "ʦiʦɒ", "ˈt̪͡sit̪͡sɒ"].iter()
+        {
+            assert_eq!( reader.from_ipa( ipa ).unwrap(), cica );
+        }
+        let itt = hungarian.from_string( "itt" ).unwrap();
+        assert_eq!( reader.from_ipa( "itː" ).unwrap(), itt );
+        assert_eq!( reader.from_ipa( "itt" ).unwrap(), itt );
+        assert_eq!( reader.from_ipa( "it:" ).unwrap(), itt );
+    }
+
+    #[test]
+    fn transcriptions_read_back()
+    {
+        let hungarian = HungarianTable::new();
+        let reader = IpaTable::new();
+        for word in ["üveg", "edz", "bridzs", "Pécsben", "hosszú", "tavasz"].iter()
+        {
+            let table = hungarian.from_string( word ).unwrap();
+            for style in [IpaStyle::Broad, IpaStyle::Narrow].iter()
+            {
+                let ipa = hungarian.to_ipa( &table, *style ).unwrap();
+                assert_eq!( reader.from_ipa( &ipa ).unwrap(), table, "{}", ipa );
+            }
+        }
+        assert_eq!( reader.from_ipa( "ɛʣ" ).unwrap(),
+                    hungarian.from_string( "edz" ).unwrap() );
+        assert_eq!( reader.from_ipa( "keːɟ ɟɛrɛk" ).unwrap(),
+                    hungarian.from_phrase( "két gyerek" ).unwrap().table );
+    }
+
+    #[test]
+    fn unknown_symbols_are_reported()
+    {
+        let reader = IpaTable::new();
+        assert_eq!( reader.from_ipa( "ɒθ" ).unwrap_err(),
+                    "Unknown IPA symbol \"θ\" at position 2" );
+        assert_eq!( reader.from_x_sampa( "aT" ).unwrap_err(),
+                    "Unknown X-SAMPA symbol \"T\" at position 1" );
+    }
+}
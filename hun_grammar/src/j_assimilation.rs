@@ -0,0 +1,23 @@
+use crate::peg;
+
+peg::parser!{
+    pub grammar j_assimilation() for str
+    {
+        //* Optional total assimilation of /j/ to a preceding /r/
+        //* in casual speech (várja -> várra).
+        pub rule input() -> Vec<(usize, usize, String)> =
+            ( p: position!() g: grapheme() q: position!() { (p, q, g) } )+
+
+        rule grapheme() -> String =
+            assimilated() / other()
+
+        rule assimilated() -> String =
+            ( "r" / "R" ) b: boundary() "j" { format!( "R{}", b ) }
+
+        rule boundary() -> &'input str =
+            $( ( "+" / "|" / "#" )? )
+
+        rule other() -> String =
+            c: $[_] { c.to_string() }
+    }
+}
@@ -0,0 +1,23 @@
+use crate::peg;
+
+peg::parser!{
+    pub grammar l_assimilation() for str
+    {
+        //* Optional total assimilation of /l/ to a following /r/
+        //* (balra -> barra).
+        pub rule input() -> Vec<(usize, usize, String)> =
+            ( p: position!() g: grapheme() q: position!() { (p, q, g) } )+
+
+        rule grapheme() -> String =
+            assimilating() / other()
+
+        rule assimilating() -> String =
+            "l" b: boundary() &( "r" / "R" ) { format!( "r{}", b ) }
+
+        rule boundary() -> &'input str =
+            $( ( "+" / "|" / "#" )? )
+
+        rule other() -> String =
+            c: $[_] { c.to_string() }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::peg;
+
+peg::parser!{
+    pub grammar l_deletion() for str
+    {
+        //* Dialectal deletion of /l/ before a consonant, the short
+        //* vowel before it being lengthened, restricted to the
+        //* contexts where it is attested: the word-initial preverbs
+        //* el, fel, föl, marked off from their verb by a compound or
+        //* morpheme boundary (el|ment -> é|ment, fel|kel -> fé|kel),
+        //* and the forms of volt (voltak -> vótak).  It does not apply
+        //* in other words, even beginning with el (alma, film, polc,
+        //* elvtárs, első).
+        pub rule input() -> Vec<(usize, usize, String)> =
+            first: word_initial()?
+            rest: ( after_boundary() /
+                    p: position!() g: other() q: position!()
+                    { vec![(p, q, g)] } )*
+        {
+            let mut result = first.unwrap_or_default();
+            result.extend( rest.concat() );
+            result
+        }
+
+        rule after_boundary() -> Vec<(usize, usize, String)> =
+            p: position!() "#" q: position!() w: word_initial()
+        {
+            let mut result = vec![(p, q, "#".to_string())];
+            result.extend( w );
+            result
+        }
+
+        // The onset and the lengthened vowel, the vowel spanning
+        // the deleted /l/:
+        rule word_initial() -> Vec<(usize, usize, String)> =
+            p: position!() c: $( ( "f" / "v" )? ) q: position!()
+            v: deleting( c ) r: position!()
+        {
+            let mut result = Vec::new();
+            if ! c.is_empty()
+            {
+                result.push( (p, q, c.to_string()) );
+            }
+            result.push( (q, r, v) );
+            result
+        }
+
+        rule deleting( onset: &str ) -> String =
+            v: preverb_vowel( onset ) "l" &( ( "+" / "|" ) consonant() )
+        { v } /
+            "o" "l" &"t" {? if onset == "v" { Ok( "ó".to_string() ) }
+                            else { Err( "volt" ) } }
+
+        // el, ël, fel, fël, föl:
+        rule preverb_vowel( onset: &str ) -> String =
+            "e" {? if onset != "v" { Ok( "é".to_string() ) } else { Err( "preverb" ) } } /
+            "ë" {? if onset != "v" { Ok( "é".to_string() ) } else { Err( "preverb" ) } } /
+            "ö" {? if onset == "f" { Ok( "ő".to_string() ) } else { Err( "preverb" ) } }
+
+        rule consonant() =
+            !( vowel() / "+" / "|" / "#" / "l" / "L" / "j" ) [_]
+
+        rule vowel() =
+            "a" / "e" / "ë" / "i" / "o" / "u" /
+            "á" / "é" / "í" / "ó" / "ú" /
+            "ö" / "ő" / "ü" / "ű"
+
+        rule other() -> String =
+            c: $[_] { c.to_string() }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::l_deletion;
+
+    fn delete( s: &str ) -> String
+    {
+        l_deletion::input( s ).unwrap().into_iter().map( | (_, _, g) | g ).collect()
+    }
+
+    #[test]
+    fn preverbs_and_volt_lose_their_l()
+    {
+        assert_eq!( delete( "el|ment" ), "é|ment" );
+        assert_eq!( delete( "fel|kel" ), "fé|kel" );
+        assert_eq!( delete( "föl+mëgy" ), "fő+mëgy" );
+        assert_eq!( delete( "voltak" ), "vótak" );
+        assert_eq!( delete( "ott#volt" ), "ott#vót" );
+        // the vowel spans the deleted /l/:
+        assert_eq!( l_deletion::input( "volt" ).unwrap(),
+                    [(0, 1, "v".to_string()), (1, 3, "ó".to_string()),
+                     (3, 4, "t".to_string())] );
+    }
+
+    #[test]
+    fn other_words_keep_their_l()
+    {
+        assert_eq!( delete( "alma" ), "alma" );
+        assert_eq!( delete( "film" ), "film" );
+        assert_eq!( delete( "polc" ), "polc" );
+        assert_eq!( delete( "ölt" ), "ölt" );
+        assert_eq!( delete( "vele" ), "vele" );
+        assert_eq!( delete( "elem" ), "elem" );
+        // el is not a preverb here:
+        assert_eq!( delete( "sok#elv" ), "sok#elv" );
+        assert_eq!( delete( "elvtárs" ), "elvtárs" );
+        assert_eq!( delete( "első" ), "első" );
+        assert_eq!( delete( "felső" ), "felső" );
+        // nor before a vowel:
+        assert_eq!( delete( "el|ad" ), "el|ad" );
+    }
+}
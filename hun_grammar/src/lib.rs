@@ -1,6 +1,22 @@
 extern crate tabular;
 extern crate peg;
 pub mod hungarian_grapheme;
+pub mod speech_style;
+pub mod orthography;
+pub mod normalization;
+pub mod spell_out;
+pub mod ipa;
+pub mod ipa_grapheme;
+pub mod x_sampa;
+pub mod dialect;
+pub mod syllable;
+pub mod stress;
+pub mod scansion;
+pub mod skeleton;
+pub mod harmony;
+pub mod suffix;
+pub mod natural_class;
+pub mod feature_chart;
 
 mod convert_graphemes;
 mod simple_grapheme;
@@ -8,6 +24,17 @@ mod obstruent_voice;
 mod orthographic_combinations;
 mod combine_identical;
 mod simplify_geminates;
+mod h_realization;
+mod l_assimilation;
+mod j_assimilation;
+mod degemination;
+mod l_deletion;
+mod segmentations;
+mod name_orthography;
+mod spelling;
+mod inventory;
 
-// convert_graphemes::convert_graphemes<'a>( s: &'a str ) ->
-//     Result<Vec<&'a str>>, String>;
+// convert_graphemes::convert_graphemes(
+//     s: &[(char, Range<usize>)], keep_final_h: &[bool],
+//     rules: &[OptionalRule], ch: &str, dialect: Dialect ) ->
+//     Result<Vec<(String, Range<usize>)>, String>;
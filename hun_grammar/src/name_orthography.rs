@@ -0,0 +1,67 @@
+use crate::peg;
+
+peg::parser!{
+    pub grammar name_orthography() for str
+    {
+        //* Rewrite the archaic spellings of (lowercased) family
+        //* names into present-day orthography:
+        //* czóbel -> cóbel, széchenyi -> szécsenyi,
+        //* batthyány -> battyány, eötvös -> ötvös,
+        //* vörösmarty -> vörösmarti.
+        pub rule input() -> Vec<(usize, usize, String)> =
+            ( p: position!() g: grapheme() q: position!() { (p, q, g) } )+
+
+        rule grapheme() -> String =
+            archaic() / isolated_y() / other()
+
+        rule archaic() -> String =
+            "tthy" &[_] { "tty".to_string() } /
+            "thy" &[_] { "ty".to_string() } /
+            "th" { "t".to_string() } /
+            "ty" &[_] { "ty".to_string() } /
+            "gy" { "gy".to_string() } /
+            "ly" { "ly".to_string() } /
+            "ny" { "ny".to_string() } /
+            "cz" { "c".to_string() } /
+            "ch" { "cs".to_string() } /
+            "ts" { "cs".to_string() } /
+            "gh" { "g".to_string() } /
+            "ew" { "ö".to_string() } /
+            "eö" { "ö".to_string() } /
+            "aa" { "á".to_string() } /
+            "ee" { "é".to_string() } /
+            "oo" { "ó".to_string() } /
+            "w" { "v".to_string() } /
+            "x" { "ks".to_string() }
+
+        // y not in a digraph is read as i (ady, kölcsey, horthy):
+        rule isolated_y() -> String =
+            "y" { "i".to_string() }
+
+        rule other() -> String =
+            c: $[_] { c.to_string() }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::name_orthography;
+
+    fn modernize( s: &str ) -> String
+    {
+        name_orthography::input( s ).unwrap().into_iter().
+            map( | (_, _, g) | g ).collect()
+    }
+
+    #[test]
+    fn archaic_spellings_are_modernized()
+    {
+        assert_eq!( modernize( "széchenyi" ), "szécsenyi" );
+        assert_eq!( modernize( "batthyány" ), "battyány" );
+        assert_eq!( modernize( "czóbel" ), "cóbel" );
+        assert_eq!( modernize( "madách" ), "madács" );
+        assert_eq!( modernize( "eötvös" ), "ötvös" );
+        assert_eq!( modernize( "ady" ), "adi" );
+    }
+}
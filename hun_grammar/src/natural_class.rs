@@ -0,0 +1,198 @@
+use std::fmt;
+
+use crate::tabular::table::{Table, Tier};
+
+// Natural classes of segments.  A specification is a set of
+// features; it is written as features separated by `,` or `+`, each
+// feature being `tier=element`, or an element alone if it belongs to
+// a single tier ("voiced + stop", "airflow=nasal, tongue=palatal").
+// The time tier is taken as a whole (`time=X` for short, `time=X X`
+// for long segments).  The table of a specification has one column
+// for each timing slot, its elements spanning all of them; it covers
+// the segments whose tables it subsumes, so an element of a contour
+// (the stop of an affricate) does not make a class on its own.
+// Contours cannot be specified: the affricates (stop slit) are
+// neither in the class "stop" nor in "slit", and a set of them has
+// no specification of its own.
+
+/// A feature specification, tier and element pairs.
+#[derive( PartialEq, Eq, Clone, Debug )]
+pub struct Specification
+{
+    pub features: Vec<(String, String)>,
+}
+
+impl fmt::Display for Specification
+{
+    fn fmt( &self, s: &mut fmt::Formatter ) -> fmt::Result
+    {
+        let features: Vec<String> =
+            self.features.iter().
+            map( | (tier, element) | format!( "{}={}", tier, element ) ).
+            collect();
+        write!( s, "{}", features.join( ", " ) )
+    }
+}
+
+impl Specification
+{
+    /// Parse a specification; `autosegments` are the tier and
+    /// element pairs that may be used.
+    pub fn parse( spec: &str, autosegments: &[(&str, &str)] ) -> Result<Self, String>
+    {
+        let mut features = Vec::new();
+        for feature in spec.split( [',', '+'] )
+        {
+            let feature = feature.trim();
+            if feature.is_empty()
+            {
+                continue;
+            }
+            let ( tier, element ) =
+                match feature.find( '=' )
+                {
+                    Some( i ) =>
+                    {
+                        let ( tier, element ) =
+                            ( feature[..i].trim(), feature[i + 1..].trim() );
+                        // the time tier is taken as a whole:
+                        if tier != "time" &&
+                            ! autosegments.contains( &( tier, element ) )
+                        {
+                            return Err( format!( "Unknown feature \"{}\"", feature ) );
+                        }
+                        ( tier.to_string(), element.to_string() )
+                    }
+                    None =>
+                    {
+                        let tiers: Vec<&str> =
+                            autosegments.iter().
+                            filter( | (_, e) | *e == feature ).
+                            map( | (t, _) | *t ).
+                            collect();
+                        match tiers.as_slice()
+                        {
+                            [tier] => ( tier.to_string(), feature.to_string() ),
+                            [] => { return Err( format!( "Unknown element \"{}\"",
+                                                         feature ) ); }
+                            _ => { return Err( format!(
+                                "Element \"{}\" on several tiers ({}), \
+                                 give it as tier=element",
+                                feature, tiers.join( ", " ) ) ); }
+                        }
+                    }
+                };
+            if ! features.contains( &( tier.clone(), element.clone() ) )
+            {
+                features.push( ( tier, element ) );
+            }
+        }
+        Ok( Specification { features } )
+    }
+
+    /// The table of the specification on the tiers `tier_names`.
+    pub fn table( &self, tier_names: &[&str],
+                  autosegments: &[(&'static str, &'static str)] )
+        -> Result<Table<'static>, String>
+    {
+        if let Some( (tier, _) ) =
+            self.features.iter().find( | (tier, _) | ! tier_names.contains( &tier.as_str() ) )
+        {
+            return Err( format!( "No segment has elements on the {} tier", tier ) );
+        }
+        let time: Vec<&str> =
+            self.features.iter().
+            filter( | (tier, _) | tier == "time" ).
+            flat_map( | (_, element) | element.split_whitespace() ).
+            collect();
+        let width = time.len().max( 1 );
+        let mut tiers = Vec::new();
+        for name in tier_names
+        {
+            let elements: Vec<&str> =
+                self.features.iter().
+                filter( | (tier, _) | tier == name ).
+                map( | (_, element) | element.as_str() ).
+                collect();
+            let tier =
+                match elements.as_slice()
+                {
+                    [] => Tier::new_gap( width ),
+                    _ if *name == "time" =>
+                    {
+                        let mut tier = Tier::new_gap( width );
+                        for ( slot, element ) in tier.slots.iter_mut().zip( &time )
+                        {
+                            slot.set_element( autosegment( autosegments, name, element )? );
+                            slot.set_left_aligned( true );
+                            slot.set_right_aligned( true );
+                        }
+                        tier
+                    }
+                    [element] =>
+                        Tier::new_slot( autosegment( autosegments, name, element )?, width ),
+                    _ =>
+                    {
+                        return Err( format!( "Several elements on the {} tier ({})",
+                                             name, elements.join( ", " ) ) );
+                    }
+                };
+            tiers.push( tier );
+        }
+        Ok( Table { tiers } )
+    }
+}
+
+// The element `element` of tier `tier`, living as long as the autosegments:
+fn autosegment( autosegments: &[(&'static str, &'static str)], tier: &str, element: &str )
+    -> Result<&'static str, String>
+{
+    autosegments.iter().
+        find( | (t, e) | *t == tier && *e == element ).
+        map( | (_, e) | *e ).
+        ok_or_else( || format!( "Unknown feature \"{}={}\"", tier, element ) )
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::Specification;
+    use crate::tabular::table::Table;
+    use crate::tabular::tabular::Tabular;
+
+    const AUTOSEGMENTS: [(&str, &str); 7] = [
+        ("tongue", "neutral"), ("lips", "neutral"), ("lips", "rounded"),
+        ("voice", "voiced"), ("airflow", "stop"), ("airflow", "slit"),
+        ("time", "X"),
+    ];
+
+    const TIERS: [&str; 4] = ["lips", "voice", "airflow", "time"];
+
+    #[test]
+    fn features_are_separated_by_commas_or_pluses()
+    {
+        let spec = Specification::parse( "voiced + stop, lips=rounded", &AUTOSEGMENTS ).
+            unwrap();
+        assert_eq!( spec.to_string(), "voice=voiced, airflow=stop, lips=rounded" );
+        assert_eq!( Specification::parse( "neutral", &AUTOSEGMENTS ).unwrap_err(),
+                    "Element \"neutral\" on several tiers (tongue, lips), \
+                     give it as tier=element" );
+        assert!( Specification::parse( "nasal", &AUTOSEGMENTS ).is_err() );
+        assert!( Specification::parse( "voice=stop", &AUTOSEGMENTS ).is_err() );
+    }
+
+    #[test]
+    fn specifications_have_a_column_for_each_timing_slot()
+    {
+        let table = | spec: &str |
+            Specification::parse( spec, &AUTOSEGMENTS ).unwrap().
+            table( &TIERS, &AUTOSEGMENTS );
+        assert_eq!( table( "voiced" ).unwrap(),
+                    Table::from_str( "_, voiced, _, _;" ).unwrap() );
+        assert_eq!( table( "voiced, time=X X" ).unwrap(),
+                    Table::from_str( "2 _, 2 voiced, 2 _, X X;" ).unwrap() );
+        assert!( table( "stop, slit" ).is_err() );
+        assert!( table( "tongue=neutral" ).is_err() );
+        assert!( table( "time=X Y" ).is_err() );
+    }
+}
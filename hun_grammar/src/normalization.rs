@@ -0,0 +1,171 @@
+use std::ops::Range;
+
+/**
+Normalization of the input of `HungarianTable::from_string`:
+composition of base letters and combining diacritics (NFC,
+restricted to the letters of Hungarian), replacement of
+look-alike letters (õ, û from Latin-1 sources), case folding
+and removal of punctuation.  Hyphens inside a word are
+turned into compound boundaries (`Kossuth-díj` -> `kossuth|díj`).
+A combining mark not forming a letter of Hungarian with its
+base letter is an error, and so is whitespace: the input is a
+single word (phrases are split into words by `from_phrase`).
+*/
+pub struct Normalized
+{
+    /// The normalized, lowercase text.
+    pub text: String,
+    /// For each character of `text`, whether it was uppercase.
+    pub uppercase: Vec<bool>,
+    /// For each character of `text`, the byte span of the
+    /// input it comes from.
+    pub spans: Vec<Range<usize>>,
+    /// The punctuation removed, with its byte offset in the input.
+    pub punctuation: Vec<(usize, char)>,
+}
+
+// Base letter and combining diacritic -> precomposed letter:
+const COMPOSITIONS: [(char, char, char); 36] = [
+    ('a', '\u{301}', 'á'), ('e', '\u{301}', 'é'), ('i', '\u{301}', 'í'),
+    ('o', '\u{301}', 'ó'), ('u', '\u{301}', 'ú'),
+    ('o', '\u{308}', 'ö'), ('u', '\u{308}', 'ü'),
+    ('e', '\u{308}', 'ë'), ('a', '\u{308}', 'ä'),
+    ('o', '\u{30b}', 'ő'), ('u', '\u{30b}', 'ű'),
+    ('o', '\u{303}', 'õ'), ('u', '\u{303}', 'ũ'),
+    ('o', '\u{302}', 'ô'), ('u', '\u{302}', 'û'),
+    ('o', '\u{30c}', 'ǒ'), ('u', '\u{30c}', 'ǔ'),
+    ('i', '\u{308}', 'ï'),
+    ('A', '\u{301}', 'Á'), ('E', '\u{301}', 'É'), ('I', '\u{301}', 'Í'),
+    ('O', '\u{301}', 'Ó'), ('U', '\u{301}', 'Ú'),
+    ('O', '\u{308}', 'Ö'), ('U', '\u{308}', 'Ü'),
+    ('E', '\u{308}', 'Ë'), ('A', '\u{308}', 'Ä'),
+    ('O', '\u{30b}', 'Ő'), ('U', '\u{30b}', 'Ű'),
+    ('O', '\u{303}', 'Õ'), ('U', '\u{303}', 'Ũ'),
+    ('O', '\u{302}', 'Ô'), ('U', '\u{302}', 'Û'),
+    ('O', '\u{30c}', 'Ǒ'), ('U', '\u{30c}', 'Ǔ'),
+    ('I', '\u{308}', 'Ï'),
+];
+
+// Letters standing in for the double acute ones in 8-bit
+// encodings and careless typing:
+fn look_alike( c: char ) -> char
+{
+    match c
+    {
+        'õ' | 'ô' | 'ǒ' => 'ő',
+        'ũ' | 'û' | 'ǔ' => 'ű',
+        'Õ' | 'Ô' | 'Ǒ' => 'Ő',
+        'Ũ' | 'Û' | 'Ǔ' => 'Ű',
+        _ => c
+    }
+}
+
+fn compose( base: char, mark: char ) -> Option<char>
+{
+    COMPOSITIONS.iter().
+        find( | (b, m, _) | *b == base && *m == mark ).
+        map( | (_, _, composed) | *composed )
+}
+
+fn is_combining( c: char ) -> bool
+{
+    ( '\u{300}'..='\u{36f}' ).contains( &c )
+}
+
+pub fn normalize( input: &str ) -> Result<Normalized, String>
+{
+    let mut result = Normalized
+    {
+        text: String::new(),
+        uppercase: Vec::new(),
+        spans: Vec::new(),
+        punctuation: Vec::new(),
+    };
+    let chars: Vec<(usize, char)> = input.char_indices().collect();
+    let mut i = 0;
+    while i < chars.len()
+    {
+        let ( start, mut c ) = chars[i];
+        let mut end = start + c.len_utf8();
+        i += 1;
+        // compose with the following combining diacritics:
+        while i < chars.len() && is_combining( chars[i].1 )
+        {
+            match compose( c, chars[i].1 )
+            {
+                Some( composed ) => { c = composed; }
+                None =>
+                {
+                    return Err( format!(
+                        "Unknown combining mark U+{:04X} on \"{}\" at position {}",
+                        chars[i].1 as u32, c, chars[i].0 ) );
+                }
+            }
+            end = chars[i].0 + chars[i].1.len_utf8();
+            i += 1;
+        }
+        c = look_alike( c );
+        if c.is_alphabetic() || c.is_numeric() || c == '+' || c == '|'
+        {
+            for lower in c.to_lowercase()
+            {
+                result.text.push( lower );
+                result.uppercase.push( c.is_uppercase() );
+                result.spans.push( start..end );
+            }
+        }
+        else if c == '-' && ! result.text.is_empty() &&
+            i < chars.len() && chars[i].1.is_alphabetic()
+        {
+            result.text.push( '|' );
+            result.uppercase.push( false );
+            result.spans.push( start..end );
+        }
+        else if c.is_whitespace()
+        {
+            return Err( format!( "Whitespace at position {} in a single word",
+                                 start ) );
+        }
+        else
+        {
+            result.punctuation.push( ( start, c ) );
+        }
+    }
+    Ok( result )
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::normalize;
+
+    #[test]
+    fn letters_are_composed_and_folded()
+    {
+        let normalized = normalize( "(Ko\u{308}ve\u{301}rõz)!" ).unwrap();
+        assert_eq!( normalized.text, "kövérőz" );
+        assert_eq!( normalized.uppercase[..2], [true, false] );
+        assert_eq!( normalized.spans[1], 2..5 );
+        assert_eq!( normalized.punctuation, [(0, '('), (13, ')'), (14, '!')] );
+    }
+
+    #[test]
+    fn hyphens_are_compound_boundaries()
+    {
+        assert_eq!( normalize( "Kossuth-díj" ).unwrap().text, "kossuth|díj" );
+    }
+
+    #[test]
+    fn unknown_combining_marks_are_errors()
+    {
+        assert!( normalize( "ke\u{327}z" ).is_err() );
+    }
+
+    #[test]
+    fn whitespace_is_not_part_of_a_word()
+    {
+        assert_eq!( normalize( "két gyerek" ).err().unwrap(),
+                    "Whitespace at position 4 in a single word" );
+        assert!( normalize( "kettő\t" ).is_err() );
+    }
+}
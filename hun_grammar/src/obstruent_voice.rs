@@ -1,12 +1,19 @@
 use crate::peg;
+use crate::inventory;
+
+// Whether `segment` triggers voice assimilation, with voicing `voiced`:
+fn triggers( segment: &str, voiced: bool ) -> bool
+{
+    inventory::triggers_voicing( segment ) &&
+        inventory::voicing( segment ) == Some( voiced )
+}
 
 peg::parser!{
     pub grammar obstruent_voice() for str
     {
         //* Perform obstruent voice assimilation.
-        pub rule input() -> String =
-            g: grapheme() i: input() { format!( "{}{}", &g, &i ) } /
-            grapheme()
+        pub rule input() -> Vec<(usize, usize, String)> =
+            ( p: position!() g: grapheme() q: position!() { (p, q, g) } )+
 
         rule grapheme() -> String =
             assimilating() / other() // non_assimilating()
@@ -32,6 +39,9 @@ peg::parser!{
             "F" { "F".to_string() } /
             "f" &voiced_obstruent() { "v".to_string() } /
             "f" { "f".to_string() } /
+            "h" &voiced_obstruent() { "ɦ".to_string() } /
+            "x" &voiced_obstruent() { "ɣ".to_string() } /
+            "x" { "x".to_string() } /
             "G" &voiceless_obstruent() { "k".to_string() } /
             "G" { "G".to_string() } /
             "g" &voiceless_obstruent() { "k".to_string() } /
@@ -88,40 +98,48 @@ peg::parser!{
             { v.to_string() }
         */
         
+        // /h/ undergoes voice assimilation, but does not trigger it,
+        // the velar fricative /x/ (Bach) does both;
+        // morpheme, compound and word boundaries are transparent:
         rule voiceless_obstruent() =
-            &"Č" /
-            &"č" /
-            &"C" /
-            &"c" /
-            &"F" /
-            &"f" /
-            &"H" /
-            &"h" /
-            &"K" /
-            &"k" /
-            &"P" /
-            &"p" /
-            &"S" /
-            &"s" /
-            &"Š" /
-            &"š" /
-            &"Ť" /
-            &"ť" /
-            &"T" /
-            &"t"
+            boundary()? &obstruent( false )
 
         rule voiced_obstruent() =
-            &"B" /
-            &"b" /
-            &"D" /
-            &"d" /
-            &"G" /
-            &"g" /
-            &"Ď" /
-            &"ď" /
-            &"Ž" /
-            &"ž" /
-            &"Z" /
-            &"z"
+            boundary()? &obstruent( true )
+
+        rule obstruent( voiced: bool ) =
+            c: $[_] {? if triggers( c, voiced ) { Ok( () ) }
+                       else { Err( "obstruent" ) } }
+
+        rule boundary() =
+            "+" / "|" / "#"
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::obstruent_voice;
+
+    fn assimilate( s: &str ) -> String
+    {
+        obstruent_voice::input( s ).unwrap().into_iter().
+            map( | (_, _, g) | g ).collect()
+    }
+
+    #[test]
+    fn voice_spreads_leftwards()
+    {
+        assert_eq!( assimilate( "kéz+től" ), "kés+től" );
+        assert_eq!( assimilate( "kút+ba" ), "kúd+ba" );
+    }
+
+    #[test]
+    fn velar_fricative_undergoes_and_triggers()
+    {
+        assert_eq!( assimilate( "bax+ból" ), "baɣ+ból" );
+        assert_eq!( assimilate( "ad+xoc" ), "at+xoc" );
+        assert_eq!( assimilate( "ah+ból" ), "aɦ+ból" );
+        assert_eq!( assimilate( "ad+hoc" ), "ad+hoc" );
     }
 }
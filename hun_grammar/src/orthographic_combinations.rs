@@ -14,53 +14,97 @@ peg::parser!{
         /// dentialveolar lateral [l] + palatal approximate [j] -> jj
         /// palatal nasal [ny, nny] + palatal approximate [j] -> nny
         /// dentialveolar nasal [n] + palatal approximate [j] -> nny
+        /// dentialveolar stop [d, t] + palatal stop [gy, ty] -> ggy, tty
         */
-        pub rule input() -> String =
-            g: grapheme() i: input() { format!( "{}{}", &g, &i ) } /
-            grapheme()
+        pub rule input() -> Vec<(usize, usize, String)> =
+            chunks: ( across_boundary() /
+                      p: position!() g: grapheme() q: position!()
+                      { vec![(p, q, g)] } )+
+        { chunks.concat() }
 
         rule grapheme() -> String =
             combination() / other()
 
-        rule combination() -> String = 
-            "Dj" { "Ď".to_string() } /
-            "DJ" { "Ď".to_string() } /
-            "dj" { "Ď".to_string() } /
-            "dJ" { "Ď".to_string() } /
-            "Tj" { "Ť".to_string() } /
-            "TJ" { "Ť".to_string() } /
-            "tj" { "Ť".to_string() } /
-            "tJ" { "Ť".to_string() } /
-            "Ts" { "C".to_string() } /
-            "TS" { "C".to_string() } /
-            "ts" { "C".to_string() } /
-            "tS" { "C".to_string() } /
-            "Tš" { "Č".to_string() } /
-            "TŠ" { "Č".to_string() } /
-            "tš" { "Č".to_string() } /
-            "tŠ" { "Č".to_string() } /
-            "ďj" { "Ď".to_string() } /
-            "Ďj" { "Ď".to_string() } /
-            "ďJ" { "Ď".to_string() } /
-            "ĎJ" { "Ď".to_string() } /
-            "ťj" { "Ť".to_string() } /
-            "Ťj" { "Ť".to_string() } /
-            "ťJ" { "Ť".to_string() } /
-            "ŤJ" { "Ť".to_string() } /
-            "lj" { "J".to_string() } /
-            "Lj" { "J".to_string() } /
-            "lJ" { "J".to_string() } /
-            "LJ" { "J".to_string() } /
-            "nj" { "Ń".to_string() } /
-            "Nj" { "Ń".to_string() } /
-            "nJ" { "Ń".to_string() } /
-            "NJ" { "Ń".to_string() } /
-            "ńj" { "Ń".to_string() } /
-            "Ńj" { "Ń".to_string() } /
-            "ńJ" { "Ń".to_string() } /
-            "ŃJ" { "Ń".to_string() }
-            
+        rule combination() -> String =
+            c1: $[_] c2: $[_]
+        {?
+            combined( c1, c2 ).map( String::from ).ok_or( "combination" )
+        }
+
+        // A morpheme, compound or word boundary between the two
+        // elements does not block the combination, it stays between
+        // the two halves of the geminate, each with the span of its
+        // letter (két gyerek -> kéď#ďerek).
+        rule across_boundary() -> Vec<(usize, usize, String)> =
+            p: position!() c1: $[_] q: position!()
+            b: $( "+" / "|" / "#" ) r: position!()
+            c2: $[_] t: position!()
+        {?
+            match combined( c1, c2 )
+            {
+                Some( geminate ) =>
+                {
+                    let half = geminate.to_lowercase();
+                    Ok( vec![(p, q, half.clone()), (q, r, b.to_string()), (r, t, half)] )
+                }
+                None => Err( "combination" )
+            }
+        }
+
         rule other() -> String =
             c:$([_]) { c.to_string() }
     }
 }
+
+// The geminate formed by two adjacent segments:
+fn combined( first: &str, second: &str ) -> Option<&'static str>
+{
+    match ( first, second )
+    {
+        ( "D" | "d" | "Ď" | "ď", "j" | "J" ) => Some( "Ď" ),
+        ( "T" | "t" | "Ť" | "ť", "j" | "J" ) => Some( "Ť" ),
+        ( "T" | "t", "s" | "S" ) => Some( "C" ),
+        ( "T" | "t", "š" | "Š" ) => Some( "Č" ),
+        ( "L" | "l", "j" | "J" ) => Some( "J" ),
+        ( "N" | "n" | "Ń" | "ń", "j" | "J" ) => Some( "Ń" ),
+        ( "D" | "d" | "T" | "t", "ď" | "Ď" ) => Some( "Ď" ),
+        ( "D" | "d" | "T" | "t", "ť" | "Ť" ) => Some( "Ť" ),
+        _ => None
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::orthographic_combinations;
+
+    fn combine( s: &str ) -> Vec<(usize, usize, String)>
+    {
+        orthographic_combinations::input( s ).unwrap()
+    }
+
+    fn text( s: &str ) -> String
+    {
+        combine( s ).into_iter().map( | (_, _, g) | g ).collect()
+    }
+
+    #[test]
+    fn letters_combine_into_geminates()
+    {
+        assert_eq!( text( "bátja" ), "báŤa" );
+        assert_eq!( text( "fáradtšág" ), "fáradČág" );
+        assert_eq!( text( "tanulja" ), "tanuJa" );
+    }
+
+    #[test]
+    fn boundary_stays_between_the_halves()
+    {
+        assert_eq!( text( "két#ďerek" ), "kéď#ďerek" );
+        assert_eq!( text( "hat+ťúk" ), "hať+ťúk" );
+        // each half keeps the position of its letter:
+        let chunks = combine( "két#ďerek" );
+        assert_eq!( chunks[2..5],
+                    [(3, 4, "ď".to_string()), (4, 5, "#".to_string()),
+                     (5, 7, "ď".to_string())] );
+    }
+}
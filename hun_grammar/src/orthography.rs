@@ -0,0 +1,25 @@
+/// The spelling conventions of the input.
+#[derive( PartialEq, Eq, Clone, Copy, Debug )]
+pub enum Orthography
+{
+    /// Present-day orthography.
+    Standard,
+    /// Family names, which keep archaic spellings
+    /// (Széchenyi, Batthyány, Czóbel).
+    Names,
+    /// Present-day orthography with the digraphs "ch" and "ph" of
+    /// loanwords (Bach, photo); elsewhere they are two letters
+    /// (lánchíd, kaphat).
+    Loanwords,
+}
+
+/// The reading of the digraph "ch" of loanwords.
+#[derive( PartialEq, Eq, Clone, Copy, Debug )]
+pub enum ChReading
+{
+    /// The velar fricative [x], spelled "h" in coda position
+    /// (Bach, pech, technika).
+    H,
+    /// "cs" (Csehov, pacsuli).
+    Cs,
+}
@@ -0,0 +1,184 @@
+use std::ops::Range;
+
+use crate::tabular::table::Table;
+
+use crate::ipa;
+
+// Quantitative scansion of verse lines converted into tables.
+// As in classical versification, a syllable is long if its vowel
+// is long (two timing slots) or it is closed by position: two or
+// more consonantal timing slots follow its vowel before the next
+// vowel, word boundaries included (a geminate has two slots).
+// Patterns are written with `-` for long and `u` for short
+// syllables; in templates, `x` (anceps) stands for either.
+
+/// A classical metre.
+#[derive( PartialEq, Eq, Clone, Copy, Debug )]
+pub enum Metre
+{
+    /// Five dactyls or spondees and a final disyllabic foot.
+    Hexameter,
+    /// Two dactyls or spondees and a long syllable, then two
+    /// dactyls and a final syllable.
+    Pentameter,
+    /// The given number of iambs or spondees, with an optional
+    /// extra final syllable.
+    Iambic( usize ),
+    /// The Sapphic hendecasyllable (-u-x-uu-u-x).
+    Sapphic,
+}
+
+impl Metre
+{
+    // The feet of the metre, each with its licensed forms:
+    fn template( &self ) -> Vec<Vec<&'static str>>
+    {
+        let dactyl_or_spondee = vec!["-uu", "--"];
+        match self
+        {
+            Metre::Hexameter =>
+            {
+                let mut feet = vec![dactyl_or_spondee; 5];
+                feet.push( vec!["-x"] );
+                feet
+            }
+            Metre::Pentameter =>
+                vec![dactyl_or_spondee.clone(), dactyl_or_spondee,
+                     vec!["-"], vec!["-uu"], vec!["-uu"], vec!["x"]],
+            Metre::Iambic( n ) =>
+            {
+                let mut feet = vec![vec!["u-", "--"]; *n];
+                feet.push( vec!["", "x"] );
+                feet
+            }
+            Metre::Sapphic =>
+                vec![vec!["-u"], vec!["-x"], vec!["-uu"], vec!["-u"], vec!["-x"]],
+        }
+    }
+}
+
+/// The scansion of a line: its long/short pattern, and its feet
+/// if it matches the metre.
+pub struct Scansion
+{
+    pub pattern: String,
+    pub feet: Option<Vec<String>>,
+}
+
+/// The vowels of `table` (by their columns) and whether
+/// their syllables are long.
+pub fn weights( table: &Table, tier_names: &[&str] ) -> Vec<(Range<usize>, bool)>
+{
+    let mut result: Vec<(Range<usize>, bool)> = Vec::new();
+    // consonantal timing slots since the last vowel:
+    let mut consonants = 0;
+    for columns in ipa::segments( table )
+    {
+        let features = ipa::features( table, tier_names, &columns );
+        if ! features.boundary.is_empty()
+        {
+            continue;
+        }
+        if features.airflow == ["open"]
+        {
+            if let Some( last ) = result.last_mut()
+            {
+                last.1 |= consonants > 1;
+            }
+            result.push( ( columns, features.timing_slots > 1 ) );
+            consonants = 0;
+        }
+        else
+        {
+            consonants += features.timing_slots;
+        }
+    }
+    if let Some( last ) = result.last_mut()
+    {
+        last.1 |= consonants > 1;
+    }
+    result
+}
+
+/// The long/short pattern of `table`.
+pub fn pattern( table: &Table, tier_names: &[&str] ) -> String
+{
+    weights( table, tier_names ).iter().
+        map( | (_, long) | if *long { '-' } else { 'u' } ).
+        collect()
+}
+
+// The division of `pattern` into the feet of `template`:
+fn divide( pattern: &str, template: &[Vec<&str>] ) -> Option<Vec<String>>
+{
+    let ( feet, rest ) =
+        match template.split_first()
+        {
+            Some( split ) => split,
+            None => { return if pattern.is_empty() { Some( Vec::new() ) } else { None }; }
+        };
+    for foot in feet
+    {
+        if foot.len() <= pattern.len() &&
+            foot.chars().zip( pattern.chars() ).
+            all( | (t, p) | t == 'x' || t == p )
+        {
+            if let Some( mut division ) = divide( &pattern[foot.len()..], rest )
+            {
+                division.insert( 0, pattern[..foot.len()].to_string() );
+                return Some( division );
+            }
+        }
+    }
+    None
+}
+
+/// Scan a line converted into `table` according to `metre`.
+pub fn scan( table: &Table, tier_names: &[&str], metre: Metre ) -> Scansion
+{
+    let pattern = pattern( table, tier_names );
+    let feet = divide( &pattern, &metre.template() );
+    Scansion { pattern, feet }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::Metre;
+    use crate::hungarian_grapheme::HungarianTable;
+    use crate::tabular::grapheme::GraphemeTabular;
+
+    #[test]
+    fn a_hexameter_is_divided_into_feet()
+    {
+        let converter = HungarianTable::new();
+        let scansion =
+            converter.scan_line( "Régi dicsőségünk, hol késel az éji homályban?",
+                                 Metre::Hexameter ).unwrap();
+        assert_eq!( scansion.pattern, "-uu-----uu-uu-u" );
+        assert_eq!( scansion.feet.unwrap(), ["-uu", "--", "--", "-uu", "-uu", "-u"] );
+    }
+
+    #[test]
+    fn syllables_are_long_by_nature_or_position()
+    {
+        let converter = HungarianTable::new();
+        // a long vowel, a geminate, two consonants across a word boundary:
+        assert_eq!( converter.scan_line( "kettő", Metre::Iambic( 1 ) ).unwrap().pattern,
+                    "--" );
+        assert_eq!( converter.scan_line( "itt a kert", Metre::Iambic( 1 ) ).unwrap().pattern,
+                    "-u-" );
+        assert_eq!( converter.scan_line( "apa", Metre::Iambic( 1 ) ).unwrap().pattern,
+                    "uu" );
+    }
+
+    #[test]
+    fn lines_not_fitting_the_metre_have_no_feet()
+    {
+        let converter = HungarianTable::new();
+        let scansion = converter.scan_line( "apa", Metre::Iambic( 1 ) ).unwrap();
+        assert!( scansion.feet.is_none() );
+        let scansion = converter.scan_line( "apám", Metre::Iambic( 1 ) ).unwrap();
+        assert_eq!( scansion.feet.unwrap(), ["u-", ""] );
+    }
+}
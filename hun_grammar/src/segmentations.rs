@@ -0,0 +1,119 @@
+// Enumeration of the grapheme segmentations of an orthographic
+// string.  `simple_grapheme` always reads the longest multigraph,
+// the other readings are obtained by inserting morpheme boundaries
+// (`+`) inside multigraphs, which blocks their formation.
+
+const MULTIGRAPHS: [&str; 16] =
+    ["ccs", "cs", "ggy", "gy", "lly", "ly", "nny", "ny",
+     "ssz", "sz", "tty", "ty", "zzs", "zs", "ch", "ph"];
+
+// Positions (indices into the character vector) before which
+// a boundary would split a multigraph:
+fn split_points( chars: &[char] ) -> Vec<usize>
+{
+    let mut result: Vec<usize> = Vec::new();
+    for start in 0..chars.len()
+    {
+        for multigraph in MULTIGRAPHS.iter()
+        {
+            let m: Vec<char> = multigraph.chars().collect();
+            if chars[start..].starts_with( &m )
+            {
+                for point in start + 1..start + m.len()
+                {
+                    if ! result.contains( &point )
+                    {
+                        result.push( point );
+                    }
+                }
+            }
+        }
+    }
+    result.sort();
+    result
+}
+
+/// The maximal number of readings returned by `segmentations`.
+pub const MAX_READINGS: usize = 256;
+
+// The reading of `chars` with a boundary before each of `points`:
+fn reading( chars: &[char], points: &[usize] ) -> String
+{
+    let mut result = String::new();
+    for ( i, c ) in chars.iter().enumerate()
+    {
+        if points.contains( &i )
+        {
+            result.push( '+' );
+        }
+        result.push( *c );
+    }
+    result
+}
+
+// Add the readings with `n` of `points` (from `from` on) split,
+// besides the ones already `chosen`, up to `MAX_READINGS`:
+fn add_readings( chars: &[char], points: &[usize], n: usize, from: usize,
+                 chosen: &mut Vec<usize>, result: &mut Vec<String> )
+{
+    if result.len() >= MAX_READINGS
+    {
+        return;
+    }
+    if chosen.len() == n
+    {
+        result.push( reading( chars, chosen ) );
+        return;
+    }
+    for i in from..points.len()
+    {
+        if points.len() - i < n - chosen.len()
+        {
+            break;
+        }
+        chosen.push( points[i] );
+        add_readings( chars, points, n, i + 1, chosen, result );
+        chosen.pop();
+    }
+}
+
+/// The readings of `s`, the greedy one first, then those with
+/// more and more multigraphs split by a morpheme boundary;
+/// at most `MAX_READINGS` of them.
+pub fn segmentations( s: &str ) -> Vec<String>
+{
+    let chars: Vec<char> = s.chars().collect();
+    let points = split_points( &chars );
+    let mut result = Vec::new();
+    for n in 0..=points.len()
+    {
+        add_readings( &chars, &points, n, 0, &mut Vec::new(), &mut result );
+        if result.len() >= MAX_READINGS
+        {
+            break;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn multigraphs_may_be_split()
+    {
+        assert_eq!( segmentations( "házsor" ), ["házsor", "ház+sor"] );
+        assert_eq!( segmentations( "kisszék" ),
+                    ["kisszék", "kis+szék", "kiss+zék", "kis+s+zék"] );
+    }
+
+    #[test]
+    fn readings_are_bounded()
+    {
+        let long = "sz".repeat( 70 );
+        assert_eq!( segmentations( &long ).len(), MAX_READINGS );
+        assert_eq!( segmentations( &long )[0], long );
+    }
+}
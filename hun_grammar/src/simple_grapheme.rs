@@ -4,12 +4,25 @@ peg::parser!{
     pub grammar simple_grapheme() for str
     {
         //* Perform grapheme conversion.
-        pub rule input() -> String =
-            g: grapheme() i: input() { format!( "{}{}", &g, &i ) } /
-            grapheme()
+        //* `ch` is the segment the digraph "ch" stands for in
+        //* loanwords; if it is `None`, "ch" and "ph" are read letter
+        //* by letter, as in native words (lánc|híd, kap+hat).
+        pub rule input( ch: Option<&str> ) -> Vec<(usize, usize, String)> =
+            ( p: position!() g: grapheme( ch ) q: position!()
+              { (p, q, g) } )+
 
-        rule grapheme() -> String =
-            complex() / simple()
+        rule grapheme( ch: Option<&str> ) -> String =
+            complex() / loan( ch ) / simple()
+
+        // letters and digraphs of loanwords:
+        rule loan( ch: Option<&str> ) -> String =
+            "ch" {? ch.map( str::to_string ).ok_or( "native ch" ) } /
+            "ph" {? ch.map( | _ | "f".to_string() ).ok_or( "native ph" ) } /
+            "qu" { "kv".to_string() } /
+            "q" { "k".to_string() } /
+            "w" { "v".to_string() } /
+            "x" { "ks".to_string() } /
+            "y" { "i".to_string() }
 
         rule complex() -> String =
             "bb" { "B".to_string() } /
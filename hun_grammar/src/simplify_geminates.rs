@@ -4,18 +4,51 @@ peg::parser!{
     pub grammar simplify_geminates() for str
     {
         //* Perform geminate simplification next to consonants
-        pub rule input( cons: &mut bool ) -> Vec<String> =
-            grapheme( cons )+
+        pub rule input( cons: &mut bool ) -> Vec<(usize, usize, String)> =
+            chunks: ( halves( cons ) /
+                      p: position!() g: grapheme( cons ) q: position!()
+                      { vec![(p, q, g)] } )+
+        { chunks.concat() }
 
         rule grapheme( cons: &mut bool ) -> String =
             v: vowel() { *cons = false; v } /
-            c: consonant( cons ) { *cons = true; c }
+            c: consonant( cons ) { *cons = true; c } /
+            boundary()
+
+        // Boundaries are transparent for simplification:
+        rule boundary() -> String =
+            b: $( "+" / "|" / "#" ) { b.to_string() }
 
         rule consonant( cons: &mut bool ) -> String =
             geminate( cons ) / simple()
 
+        // The halves of a geminate across a boundary are simplified
+        // as well, the second one being kept (part+tól -> par+tól):
+        rule halves( cons: &mut bool ) -> Vec<(usize, usize, String)> =
+            h: identical_halves() &( boundary()? consonant( cons ) )
+        {
+            *cons = true;
+            vec![h[1].clone(), h[2].clone()]
+        } /
+            h: identical_halves()
+        {
+            let simplified = *cons;
+            *cons = true;
+            if simplified { vec![h[1].clone(), h[2].clone()] }
+            else { h }
+        }
+
+        rule identical_halves() -> Vec<(usize, usize, String)> =
+            p: position!() h1: simple() q: position!()
+            b: boundary() r: position!()
+            h2: simple() t: position!()
+        {?
+            if h1 == h2 { Ok( vec![(p, q, h1), (q, r, b), (r, t, h2)] ) }
+            else { Err( "identical halves" ) }
+        }
+
         rule vowel() -> String =
-            s: $("a" / "e" / "i" / "o" / "u"
+            s: $("a" / "e" / "ë" / "i" / "o" / "u"
                  / "á" / "é" / "í" / "ó" / "ú"
                  / "ö" / "ő" / "ü" / "ű")
         { s.to_string() }
@@ -29,6 +62,7 @@ peg::parser!{
             "g" { "g".to_string() } /
             "ď" { "ď".to_string() } /
             "h" { "h".to_string() } /
+            "ɦ" { "ɦ".to_string() } /
             "j" { "j".to_string() } /
             "k" { "k".to_string() } /
             "l" { "l".to_string() } /
@@ -45,144 +79,145 @@ peg::parser!{
             "v" { "v".to_string() } /
             "w" { "w".to_string() } /
             "x" { "x".to_string() } /
+            "ɣ" { "ɣ".to_string() } /
             "y" { "y".to_string() } /
             "ž" { "ž".to_string() } /
             "z" { "z".to_string() }
 
         rule geminate( cons: &mut bool ) -> String =
-            "B" &consonant( cons ) { "b".to_string() } /
+            "B" &( boundary()? consonant( cons ) ) { "b".to_string() } /
             "B"
         {
             if *cons { "b".to_string() }
             else { "B".to_string() }
         } /
-            "Č" &consonant( cons ) { "č".to_string() } /
+            "Č" &( boundary()? consonant( cons ) ) { "č".to_string() } /
             "Č"
         {
             if *cons { "č".to_string() }
             else { "Č".to_string() }
         } /
-            "C" &consonant( cons ) { "c".to_string() } /
+            "C" &( boundary()? consonant( cons ) ) { "c".to_string() } /
             "C"
         {
             if *cons { "c".to_string() }
             else { "C".to_string() }
         } /
-            "D" &consonant( cons ) { "d".to_string() } /
+            "D" &( boundary()? consonant( cons ) ) { "d".to_string() } /
             "D"
         {
             if *cons { "d".to_string() }
             else { "D".to_string() }
         } /
-            "F" &consonant( cons ) { "f".to_string() } /
+            "F" &( boundary()? consonant( cons ) ) { "f".to_string() } /
             "F"
         {
             if *cons { "f".to_string() }
             else { "F".to_string() }
         } /
-            "G" &consonant( cons ) { "g".to_string() } /
+            "G" &( boundary()? consonant( cons ) ) { "g".to_string() } /
             "G"
         {
             if *cons { "g".to_string() }
             else { "G".to_string() }
         } /
-            "Ď" &consonant( cons ) { "ď".to_string() } /
+            "Ď" &( boundary()? consonant( cons ) ) { "ď".to_string() } /
             "Ď"
         {
             if *cons { "ď".to_string() }
             else { "Ď".to_string() }
         } /
-            "H" &consonant( cons ) { "h".to_string() } /
+            "H" &( boundary()? consonant( cons ) ) { "h".to_string() } /
             "H"
         {
             if *cons { "h".to_string() }
             else { "H".to_string() }
         } /
-            "J" &consonant( cons ) { "j".to_string() } /
+            "J" &( boundary()? consonant( cons ) ) { "j".to_string() } /
             "J"
         {
             if *cons { "j".to_string() }
             else { "J".to_string() }
         } /
-            "K" &consonant( cons ) { "k".to_string() } /
+            "K" &( boundary()? consonant( cons ) ) { "k".to_string() } /
             "K"
         {
             if *cons { "k".to_string() }
             else { "K".to_string() }
         } /
-            "L" &consonant( cons ) { "l".to_string() } /
+            "L" &( boundary()? consonant( cons ) ) { "l".to_string() } /
             "L"
         {
             if *cons { "l".to_string() }
             else { "L".to_string() }
         } /
-            "M" &consonant( cons ) { "m".to_string() } /
+            "M" &( boundary()? consonant( cons ) ) { "m".to_string() } /
             "M"
         {
             if *cons { "m".to_string() }
             else { "M".to_string() }
         } /
-            "Ń" &consonant( cons ) { "ń".to_string() } /
+            "Ń" &( boundary()? consonant( cons ) ) { "ń".to_string() } /
             "Ń"
         {
             if *cons { "ń".to_string() }
             else { "Ń".to_string() }
         } /
-            "N" &consonant( cons ) { "n".to_string() } /
+            "N" &( boundary()? consonant( cons ) ) { "n".to_string() } /
             "N"
         {
             if *cons { "n".to_string() }
             else { "N".to_string() }
         } /
-            "P" &consonant( cons ) { "p".to_string() } /
+            "P" &( boundary()? consonant( cons ) ) { "p".to_string() } /
             "P"
         {
             if *cons { "p".to_string() }
             else { "P".to_string() }
         } /
-            "R" &consonant( cons ) { "r".to_string() } /
+            "R" &( boundary()? consonant( cons ) ) { "r".to_string() } /
             "R"
         {
             if *cons { "r".to_string() }
             else { "R".to_string() }
         } /
-            "S" &consonant( cons ) { "s".to_string() } /
+            "S" &( boundary()? consonant( cons ) ) { "s".to_string() } /
             "S"
         {
             if *cons { "s".to_string() }
             else { "S".to_string() }
         } /
-            "Š" &consonant( cons ) { "š".to_string() } /
+            "Š" &( boundary()? consonant( cons ) ) { "š".to_string() } /
             "Š"
         {
             if *cons { "š".to_string() }
             else { "Š".to_string() }
         } /
-            "Ť" &consonant( cons ) { "ť".to_string() } /
+            "Ť" &( boundary()? consonant( cons ) ) { "ť".to_string() } /
             "Ť"
         {
             if *cons { "ť".to_string() }
             else { "Ť".to_string() }
         } /
-            "T" &consonant( cons ) { "t".to_string() } /
+            "T" &( boundary()? consonant( cons ) ) { "t".to_string() } /
             "T"
         {
             if *cons { "t".to_string() }
             else { "T".to_string() }
         } /
-            "V" &consonant( cons ) { "v".to_string() } /
+            "V" &( boundary()? consonant( cons ) ) { "v".to_string() } /
             "V"
         {
             if *cons { "v".to_string() }
             else { "V".to_string() }
         } /
-            "Ž" &consonant( cons ) { "ž".to_string() } /
+            "Ž" &( boundary()? consonant( cons ) ) { "ž".to_string() } /
             "Ž"
         {
             if *cons { "ž".to_string() }
             else { "Ž".to_string() }
         } /
-            "Z" &consonant( cons ) { "z".to_string() } /
+            "Z" &( boundary()? consonant( cons ) ) { "z".to_string() } /
             "Z"
         {
             if *cons { "z".to_string() }
@@ -190,3 +225,31 @@ peg::parser!{
         }
     }
 }
+
+#[cfg(test)]
+mod tests
+{
+    use super::simplify_geminates;
+
+    fn simplify( s: &str ) -> String
+    {
+        let mut cons = false;
+        simplify_geminates::input( s, &mut cons ).unwrap().into_iter().
+            map( | (_, _, g) | g ).collect()
+    }
+
+    #[test]
+    fn geminates_next_to_consonants_are_short()
+    {
+        assert_eq!( simplify( "aLma" ), "alma" );
+        assert_eq!( simplify( "jobBra" ), "jobbra" );
+        assert_eq!( simplify( "iTas" ), "iTas" );
+    }
+
+    #[test]
+    fn halves_next_to_consonants_are_short()
+    {
+        assert_eq!( simplify( "part+tól" ), "par+tól" );
+        assert_eq!( simplify( "kéz+zel" ), "kéz+zel" );
+    }
+}
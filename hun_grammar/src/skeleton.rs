@@ -0,0 +1,158 @@
+use std::ops::Range;
+
+use crate::tabular::table::Table;
+
+use crate::ipa;
+use crate::syllable;
+
+// The skeleton of converted tables: the timing slots of the time
+// tier, labelled V if their segment is a vowel (open airflow, front
+// or back tongue) and C otherwise.  A segment linked to two slots
+// (a long vowel or a geminate) is doubly linked.  Nuclei and codas
+// are moraic (each of their slots is a mora), onsets are not.
+
+/// A timing slot: its columns, its label, the index of its
+/// segment in `Skeleton::segments`, and whether it is a mora.
+#[derive( PartialEq, Eq, Clone, Debug )]
+pub struct TimingSlot
+{
+    pub columns: Range<usize>,
+    pub label: char,
+    pub segment: usize,
+    pub mora: bool,
+}
+
+/// The timing slots of a table and the columns of the
+/// segments they are linked to (boundaries excluded).
+#[derive( PartialEq, Eq, Clone, Debug )]
+pub struct Skeleton
+{
+    pub slots: Vec<TimingSlot>,
+    pub segments: Vec<Range<usize>>,
+}
+
+impl Skeleton
+{
+    /// The labels of the slots (kassza -> CVCCV).
+    pub fn cv( &self ) -> String
+    {
+        self.slots.iter().map( | slot | slot.label ).collect()
+    }
+
+    /// The labels of the slots, a doubly linked segment written
+    /// with `:` after its label (kassza -> CVC:V).
+    pub fn shape( &self ) -> String
+    {
+        let mut result = String::new();
+        for ( i, slot ) in self.slots.iter().enumerate()
+        {
+            if i > 0 && self.slots[i - 1].segment == slot.segment
+            {
+                result.push( ':' );
+            }
+            else
+            {
+                result.push( slot.label );
+            }
+        }
+        result
+    }
+
+    /// The indices (in `segments`) of the segments linked
+    /// to more than one slot.
+    pub fn doubly_linked( &self ) -> Vec<usize>
+    {
+        let mut result: Vec<usize> = Vec::new();
+        for ( i, slot ) in self.slots.iter().enumerate()
+        {
+            if i > 0 && self.slots[i - 1].segment == slot.segment &&
+                result.last() != Some( &slot.segment )
+            {
+                result.push( slot.segment );
+            }
+        }
+        result
+    }
+
+    /// The weight of the table in morae.
+    pub fn morae( &self ) -> usize
+    {
+        self.slots.iter().filter( | slot | slot.mora ).count()
+    }
+}
+
+/// The skeleton of `table`.
+pub fn skeleton( table: &Table, tier_names: &[&str] ) -> Result<Skeleton, String>
+{
+    let time =
+        tier_names.iter().position( | n | *n == "time" ).
+        ok_or_else( || "No time tier".to_string() )?;
+    let constituents = syllable::constituents( table, tier_names )?;
+    let mut slots = Vec::new();
+    let mut segments = Vec::new();
+    for columns in ipa::segments( table )
+    {
+        let features = ipa::features( table, tier_names, &columns );
+        if ! features.boundary.is_empty()
+        {
+            continue;
+        }
+        let vowel =
+            features.airflow == ["open"] &&
+            features.tongue.iter().all( | t | *t == "front" || *t == "back" );
+        for slot in syllable::timing_slots( &table.tiers[time], &columns )
+        {
+            let mora =
+                constituents.iter().any( | c |
+                    c.role != "onset" &&
+                    c.columns.start <= slot.start && slot.end <= c.columns.end );
+            slots.push( TimingSlot
+                        {
+                            columns: slot,
+                            label: if vowel { 'V' } else { 'C' },
+                            segment: segments.len(),
+                            mora,
+                        } );
+        }
+        segments.push( columns );
+    }
+    Ok( Skeleton { slots, segments } )
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::Skeleton;
+    use crate::hungarian_grapheme::HungarianTable;
+    use crate::tabular::grapheme::GraphemeTabular;
+
+    fn skeleton( word: &'static str ) -> Skeleton
+    {
+        let converter = HungarianTable::new();
+        converter.skeleton( &converter.from_string( word ).unwrap() ).unwrap()
+    }
+
+    #[test]
+    fn geminates_and_long_vowels_are_doubly_linked()
+    {
+        let kassza = skeleton( "kassza" );
+        assert_eq!( kassza.cv(), "CVCCV" );
+        assert_eq!( kassza.shape(), "CVC:V" );
+        assert_eq!( kassza.doubly_linked(), [2] );
+        let kar = skeleton( "kár" );
+        assert_eq!( kar.cv(), "CVVC" );
+        assert_eq!( kar.shape(), "CV:C" );
+        assert_eq!( kar.doubly_linked(), [1] );
+        assert_eq!( skeleton( "kert" ).doubly_linked(), Vec::<usize>::new() );
+    }
+
+    #[test]
+    fn onsets_are_not_moraic()
+    {
+        assert_eq!( skeleton( "kassza" ).morae(), 3 );
+        assert_eq!( skeleton( "kár" ).morae(), 3 );
+        assert_eq!( skeleton( "apa" ).morae(), 2 );
+        // boundaries have no slots:
+        assert_eq!( skeleton( "ház+ban" ).cv(), "CVVCCVC" );
+    }
+}
@@ -0,0 +1,55 @@
+use std::fmt;
+
+/// Speech rate/register, selecting the optional rules
+/// that may apply in a conversion.
+#[derive( PartialEq, Eq, Clone, Copy, Debug )]
+pub enum SpeechStyle
+{
+    Careful,
+    Normal,
+    Casual,
+}
+
+/// Optional (rate-dependent) processes.
+#[derive( PartialEq, Eq, std::hash::Hash, Clone, Copy,
+          PartialOrd, Ord, Debug )]
+pub enum OptionalRule
+{
+    /// l + r -> rr (balra -> barra)
+    LAssimilation,
+    /// r + j -> rr (várja -> várra)
+    JAssimilation,
+    /// geminates are shortened (itt -> it)
+    Degemination,
+}
+
+impl SpeechStyle
+{
+    /// The optional rules that may apply in this style;
+    /// careful speech applies none of them.
+    pub fn optional_rules( &self ) -> Vec<OptionalRule>
+    {
+        match self
+        {
+            SpeechStyle::Careful => Vec::new(),
+            SpeechStyle::Normal => vec![OptionalRule::LAssimilation],
+            SpeechStyle::Casual =>
+                vec![OptionalRule::LAssimilation,
+                     OptionalRule::JAssimilation,
+                     OptionalRule::Degemination],
+        }
+    }
+}
+
+impl fmt::Display for OptionalRule
+{
+    fn fmt( &self, s: &mut fmt::Formatter ) -> fmt::Result
+    {
+        match self
+        {
+            OptionalRule::LAssimilation => write!( s, "l-assimilation" ),
+            OptionalRule::JAssimilation => write!( s, "j-assimilation" ),
+            OptionalRule::Degemination => write!( s, "degemination" ),
+        }
+    }
+}
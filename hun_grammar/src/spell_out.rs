@@ -0,0 +1,368 @@
+use std::collections::HashMap as Map;
+use std::iter::FromIterator;
+
+use crate::harmony::{self, Vowel};
+use crate::suffix;
+
+/**
+Spelling out the parts of a text that are not written with letters:
+cardinal and ordinal numerals (`1848-ban`, `3.`), abbreviations
+(`stb.`, `km`) and acronyms, read letter by letter (`MTA` ->
+`em-té-á`).  The parts of a numeral are joined by morpheme
+boundaries (`1848` -> `ezer+nyolc+száz+negyven+nyolc`).  A suffix
+attached with a hyphen is made to agree in vowel harmony with the
+spelled-out form (`5-ben` -> `öt+ben`, `1848-ben` ->
+`ezer+nyolc+száz+negyven+nyolc+ban`).
+*/
+pub struct SpellOut
+{
+    abbreviations: Map<String, String>,
+}
+
+const UNITS: [&str; 10] =
+    ["", "egy", "kettő", "három", "négy",
+     "öt", "hat", "hét", "nyolc", "kilenc"];
+
+// tens standing alone and followed by units:
+const TENS: [(&str, &str); 10] =
+    [("", ""), ("tíz", "tizen"), ("húsz", "huszon"),
+     ("harminc", "harminc"), ("negyven", "negyven"),
+     ("ötven", "ötven"), ("hatvan", "hatvan"),
+     ("hetven", "hetven"), ("nyolcvan", "nyolcvan"),
+     ("kilencven", "kilencven")];
+
+// powers of a thousand (long scale), up to the range of `u64`:
+const GROUPS: [&str; 7] =
+    ["", "ezer", "millió", "milliárd", "billió", "billiárd", "trillió"];
+
+// Final parts of cardinals and the corresponding ordinal forms,
+// longer parts first:
+const ORDINAL_ENDINGS: [(&str, &str); 26] =
+    [("kilencven", "kilencvenedik"), ("nyolcvan", "nyolcvanadik"),
+     ("milliárd", "milliárdodik"), ("billiárd", "billiárdodik"),
+     ("trillió", "trilliomodik"), ("harminc", "harmincadik"),
+     ("negyven", "negyvenedik"), ("kilenc", "kilencedik"),
+     ("hetven", "hetvenedik"), ("hatvan", "hatvanadik"),
+     ("ötven", "ötvenedik"), ("millió", "milliomodik"),
+     ("billió", "billiomodik"),
+     ("három", "harmadik"), ("kettő", "kettedik"),
+     ("nyolc", "nyolcadik"), ("nulla", "nulladik"),
+     ("négy", "negyedik"), ("ezer", "ezredik"),
+     ("száz", "századik"), ("húsz", "huszadik"),
+     ("tíz", "tizedik"), ("egy", "egyedik"),
+     ("hét", "hetedik"), ("hat", "hatodik"), ("öt", "ötödik")];
+
+// Suffixes with a three-way (back/front unrounded/front rounded)
+// alternation of their vowel:
+const THREE_WAY_SUFFIXES: [&str; 18] =
+    ["hoz", "hez", "höz", "szor", "szer", "ször",
+     "on", "en", "ön", "os", "es", "ös",
+     "ot", "et", "öt", "ok", "ek", "ök"];
+
+// Letter names as read in acronyms, vowels being long (EU -> é-ú):
+const LETTER_NAMES: [(char, &str); 35] =
+    [('a', "á"), ('á', "á"), ('b', "bé"), ('c', "cé"), ('d', "dé"),
+     ('e', "é"), ('é', "é"), ('f', "ef"), ('g', "gé"), ('h', "há"),
+     ('i', "í"), ('í', "í"), ('j', "jé"), ('k', "ká"), ('l', "el"),
+     ('m', "em"), ('n', "en"), ('o', "ó"), ('ó', "ó"), ('ö', "ő"),
+     ('ő', "ő"), ('p', "pé"), ('q', "kú"), ('r', "er"), ('s', "es"),
+     ('t', "té"), ('u', "ú"), ('ú', "ú"), ('ü', "ű"), ('ű', "ű"),
+     ('v', "vé"), ('w', "duplavé"), ('x', "iksz"), ('y', "ipszilon"),
+     ('z', "zé")];
+
+// Cardinals below a thousand, their parts joined by morpheme
+// boundaries; `last` tells whether the number ends the numeral
+// (2 is "kettő" there, "két" otherwise):
+fn below_thousand( n: u64, last: bool ) -> String
+{
+    let hundreds = ( n / 100 ) as usize;
+    let tens = ( n % 100 / 10 ) as usize;
+    let units = ( n % 10 ) as usize;
+    let mut parts: Vec<&str> = Vec::new();
+    match hundreds
+    {
+        0 => (),
+        1 => parts.push( "száz" ),
+        2 => parts.extend( ["két", "száz"] ),
+        _ => parts.extend( [UNITS[hundreds], "száz"] )
+    }
+    if units == 0
+    {
+        parts.push( TENS[tens].0 );
+    }
+    else
+    {
+        parts.push( TENS[tens].1 );
+        parts.push( if units == 2 && ! last { "két" } else { UNITS[units] } );
+    }
+    parts.retain( | part | ! part.is_empty() );
+    parts.join( "+" )
+}
+
+/// The cardinal numeral `n` spelled out, its parts joined by
+/// morpheme boundaries.  Above 2000, groups of three digits are
+/// separated by hyphens (két+ezer-tizen+kilenc).
+pub fn cardinal( n: u64 ) -> String
+{
+    if n == 0
+    {
+        return "nulla".to_string();
+    }
+    let mut groups: Vec<String> = Vec::new();
+    let mut rest = n;
+    let mut group = 0;
+    while rest > 0
+    {
+        let value = rest % 1000;
+        if value > 0
+        {
+            let spelled =
+                if group == 0 { below_thousand( value, true ) }
+                else if group == 1 && value == 1 { GROUPS[1].to_string() }
+                else
+                {
+                    format!( "{}+{}", below_thousand( value, false ), GROUPS[group] )
+                };
+            groups.insert( 0, spelled );
+        }
+        rest /= 1000;
+        group += 1;
+    }
+    if n > 2000 { groups.join( "-" ) } else { groups.join( "+" ) }
+}
+
+/// The ordinal numeral `n` spelled out (`3.` -> harmadik).
+pub fn ordinal( n: u64 ) -> String
+{
+    match n
+    {
+        1 => "első".to_string(),
+        2 => "második".to_string(),
+        _ =>
+        {
+            let spelled = cardinal( n );
+            for ( ending, ordinal_ending ) in ORDINAL_ENDINGS.iter()
+            {
+                if spelled.ends_with( ending )
+                {
+                    return format!(
+                        "{}{}",
+                        &spelled[..spelled.len() - ending.len()],
+                        ordinal_ending );
+                }
+            }
+            spelled
+        }
+    }
+}
+
+// The vowel projection of a spelled-out word, read from its
+// letters (the columns are letter positions):
+fn vowels( word: &str ) -> Vec<Vowel>
+{
+    let mut result = Vec::new();
+    for ( i, c ) in word.chars().enumerate()
+    {
+        let ( back, rounded ) =
+            match c
+            {
+                'a' | 'á' | 'o' | 'ó' | 'u' | 'ú' => ( true, true ),
+                'ö' | 'ő' | 'ü' | 'ű' => ( false, true ),
+                'e' | 'é' | 'i' | 'í' => ( false, false ),
+                _ => { continue; }
+            };
+        result.push( Vowel { columns: i..i + 1, back, rounded,
+                             neutral: "éií".contains( c ) } );
+    }
+    result
+}
+
+// The template of a suffix (see `suffix`), its vowels replaced
+// by archiphonemes:
+fn template( suffix: &str ) -> String
+{
+    let three_way = THREE_WAY_SUFFIXES.contains( &suffix );
+    suffix.chars().map( | c |
+    {
+        match c
+        {
+            'e' if three_way => 'O',
+            'a' | 'e' => 'A',
+            'á' | 'é' => 'Á',
+            'o' | 'ö' => 'O',
+            'ó' | 'ő' => 'Ó',
+            'u' | 'ü' => 'U',
+            'ú' | 'ű' => 'Ú',
+            _ => c
+        }
+    } ).collect()
+}
+
+// The suffix agreeing in vowel harmony with the spelled-out `word`:
+fn harmonize( suffix: &str, word: &str ) -> Option<String>
+{
+    suffix::allomorph( &template( suffix ), harmony::suffix_class( &vowels( word ) ) ).ok()
+}
+
+fn is_acronym( token: &str ) -> bool
+{
+    token.chars().count() > 1 &&
+        token.chars().all( | c | c.is_alphabetic() && c.is_uppercase() )
+}
+
+fn acronym( token: &str ) -> String
+{
+    let mut names: Vec<&str> = Vec::new();
+    for c in token.chars().flat_map( | c | c.to_lowercase() )
+    {
+        match LETTER_NAMES.iter().find( | (letter, _) | *letter == c )
+        {
+            Some( (_, name) ) => names.push( name ),
+            None => { return token.to_string(); }
+        }
+    }
+    names.join( "-" )
+}
+
+impl Default for SpellOut
+{
+    fn default() -> Self
+    {
+        SpellOut::new()
+    }
+}
+
+impl SpellOut
+{
+    /// A spelling-out layer with the common abbreviations.
+    pub fn new() -> Self
+    {
+        SpellOut
+        {
+            abbreviations: Map::from_iter(
+                vec![("stb.", "és a többi"), ("pl.", "például"),
+                     ("kb.", "körülbelül"), ("ill.", "illetve"),
+                     ("ún.", "úgynevezett"), ("vö.", "vesd össze"),
+                     ("u.", "utca"), ("dr.", "doktor"), ("Dr.", "doktor"),
+                     ("sz.", "szám"), ("db", "darab"), ("Ft", "forint"),
+                     ("km", "kilométer"), ("kg", "kilogramm"),
+                     ("cm", "centiméter"), ("mm", "milliméter")].
+                into_iter().
+                map( | (a, e) | (a.to_string(), e.to_string()) ) ),
+        }
+    }
+
+    pub fn add_abbreviation( &mut self, abbreviation: &str, expansion: &str )
+    {
+        self.abbreviations.insert(
+            abbreviation.to_string(), expansion.to_string() );
+    }
+
+    // A token (without trailing punctuation) spelled out,
+    // or `None` if it is an ordinary word:
+    fn spell_token( &self, token: &str ) -> Option<String>
+    {
+        if let Some( expansion ) = self.abbreviations.get( token )
+        {
+            return Some( expansion.clone() );
+        }
+        let ( stem, suffix ) =
+            match token.find( '-' )
+            {
+                Some( i ) => ( &token[..i], Some( &token[i + 1..] ) ),
+                None => ( token, None )
+            };
+        let spelled =
+            if let Some( expansion ) = self.abbreviations.get( stem )
+            {
+                expansion.clone()
+            }
+            else if stem.ends_with( '.' ) &&
+                stem.len() > 1 &&
+                stem[..stem.len() - 1].chars().all( | c | c.is_ascii_digit() )
+            {
+                ordinal( stem[..stem.len() - 1].parse::<u64>().ok()? )
+            }
+            else if ! stem.is_empty() &&
+                stem.chars().all( | c | c.is_ascii_digit() )
+            {
+                cardinal( stem.parse::<u64>().ok()? )
+            }
+            else if is_acronym( stem )
+            {
+                acronym( stem )
+            }
+            else { return None; };
+        match suffix
+        {
+            Some( suffix ) =>
+            {
+                let suffix = harmonize( &suffix.to_lowercase(), &spelled )?;
+                if is_acronym( stem )
+                {
+                    Some( format!( "{}-{}", spelled, suffix ) )
+                }
+                else
+                {
+                    Some( format!( "{}+{}", spelled, suffix ) )
+                }
+            }
+            None => Some( spelled )
+        }
+    }
+
+    /// `text` with its numerals, abbreviations and acronyms
+    /// spelled out, other words and punctuation unchanged.
+    pub fn spell_out( &self, text: &str ) -> String
+    {
+        let mut words: Vec<String> = Vec::new();
+        for token in text.split_whitespace()
+        {
+            if let Some( spelled ) = self.spell_token( token )
+            {
+                words.push( spelled );
+                continue;
+            }
+            // try again without trailing punctuation:
+            let bare = token.trim_end_matches(
+                | c: char | ",;:!?)\"”»".contains( c ) );
+            match self.spell_token( bare )
+            {
+                Some( spelled ) =>
+                    words.push( format!( "{}{}", spelled, &token[bare.len()..] ) ),
+                None => words.push( token.to_string() )
+            }
+        }
+        words.join( " " )
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn cardinals_cover_the_range_of_u64()
+    {
+        assert_eq!( cardinal( 1848 ), "ezer+nyolc+száz+negyven+nyolc" );
+        assert_eq!( cardinal( 222 ), "két+száz+huszon+kettő" );
+        assert_eq!( cardinal( 2019 ), "két+ezer-tizen+kilenc" );
+        assert_eq!( cardinal( 1_000_000_000_000 ), "egy+billió" );
+        assert_eq!( cardinal( 2_000_000_000_000_000 ), "két+billiárd" );
+        assert!( cardinal( u64::MAX ).starts_with( "tizen+nyolc+trillió-" ) );
+        assert_eq!( ordinal( 1_000_000_000_000 ), "egy+billiomodik" );
+    }
+
+    #[test]
+    fn text_is_spelled_out()
+    {
+        let spell_out = SpellOut::default();
+        assert_eq!( spell_out.spell_out( "5-ben, stb." ), "öt+ben, és a többi" );
+        assert_eq!( spell_out.spell_out( "3-hoz 6-hoz 1848-ben" ),
+                    "három+hoz hat+hoz ezer+nyolc+száz+negyven+nyolc+ban" );
+        assert_eq!( spell_out.spell_out( "1000000000000" ), "egy+billió" );
+        assert_eq!( spell_out.spell_out( "az MTA 3. ülése" ),
+                    "az em-té-á harmadik ülése" );
+    }
+}
@@ -0,0 +1,178 @@
+// Generation of the orthographic candidates of a segment sequence
+// (segments and boundaries in the notation of `from_grapheme`).
+// Candidates are built left to right from spelling patterns; each
+// alternative of a pattern adds its index to the cost of the
+// candidate, so the usual spelling of a segment is preferred.
+// Candidates are not checked here: etymological spellings are
+// proposed wherever they might apply, and those not converting
+// back to the segments are filtered out by the caller.
+
+use std::collections::HashMap as Map;
+
+use crate::inventory;
+
+// Segment sequences and their spellings, in order of preference:
+const PATTERNS: [(&[&str], &[&str]); 68] = [
+    // vowels
+    (&["a"], &["a"]), (&["e"], &["e"]), (&["i"], &["i"]),
+    (&["o"], &["o"]), (&["u"], &["u"]), (&["ö"], &["ö"]),
+    (&["ü"], &["ü"]), (&["á"], &["á"]), (&["é"], &["é"]),
+    (&["í"], &["í"]), (&["ó"], &["ó"]), (&["ú"], &["ú"]),
+    (&["ő"], &["ő"]), (&["ű"], &["ű"]),
+    // stops and affricates
+    (&["b"], &["b"]), (&["d"], &["d"]), (&["g"], &["g"]),
+    (&["p"], &["p"]), (&["t"], &["t"]), (&["k"], &["k"]),
+    (&["c"], &["c"]), (&["č"], &["cs"]),
+    (&["ď"], &["gy"]), (&["ť"], &["ty"]),
+    (&["B"], &["bb"]), (&["D"], &["dd"]), (&["G"], &["gg"]),
+    (&["P"], &["pp"]), (&["T"], &["tt"]), (&["K"], &["kk"]),
+    (&["C"], &["cc", "tsz", "dsz"]),
+    (&["Č"], &["ccs", "ts", "ds"]),
+    (&["Ď"], &["ggy", "dj", "gyj", "dgy", "tgy"]),
+    (&["Ť"], &["tty", "tj", "tyj", "tgy", "dty"]),
+    (&["d", "z"], &["dz", "c"]),
+    (&["d", "ž"], &["dzs", "cs"]),
+    // fricatives
+    (&["v"], &["v"]), (&["f"], &["f"]), (&["z"], &["z"]),
+    (&["s"], &["sz"]), (&["ž"], &["zs"]), (&["š"], &["s"]),
+    (&["V"], &["vv"]), (&["F"], &["ff"]), (&["Z"], &["zz"]),
+    (&["S"], &["ssz"]), (&["Ž"], &["zzs"]), (&["Š"], &["ss"]),
+    (&["h"], &["h"]), (&["H"], &["hh"]), (&["ɦ"], &["h"]),
+    (&["x"], &["ch", "h"]), (&["ɣ"], &["ch", "h"]),
+    // sonorants
+    (&["j"], &["j", "ly"]), (&["J"], &["jj", "lly", "lj"]),
+    (&["l"], &["l"]), (&["L"], &["ll"]),
+    (&["m"], &["m"]), (&["M"], &["mm"]),
+    (&["n"], &["n"]), (&["N"], &["nn"]),
+    (&["ń"], &["ny"]), (&["Ń"], &["nny", "nj", "nyj"]),
+    (&["r"], &["r"]), (&["R"], &["rr"]),
+    // boundaries
+    (&["+"], &["+"]), (&["|"], &["|"]), (&["#"], &[" "]),
+];
+
+// Obstruents spelled with the letters of their voicing counterpart
+// before an obstruent (etymological spelling of voice assimilation):
+const COUNTERPARTS: [(&str, &str); 28] = [
+    ("b", "p"), ("d", "t"), ("g", "k"), ("ď", "ty"), ("z", "sz"),
+    ("ž", "s"), ("v", "f"),
+    ("p", "b"), ("t", "d"), ("k", "g"), ("ť", "gy"), ("s", "z"),
+    ("š", "zs"), ("f", "v"),
+    ("B", "pp"), ("D", "tt"), ("G", "kk"), ("Ď", "tty"), ("Z", "ssz"),
+    ("Ž", "ss"), ("V", "ff"),
+    ("P", "bb"), ("T", "dd"), ("K", "gg"), ("Ť", "ggy"), ("S", "zz"),
+    ("Š", "zzs"), ("F", "vv"),
+];
+
+// The halves of geminates formed from two letters across
+// a boundary (két gyerek -> kéď#ďerek), and the letter pairs:
+const SPLITS: [(&str, &[(&str, &str)]); 6] = [
+    ("c", &[("t", "sz"), ("d", "sz")]),
+    ("č", &[("t", "s"), ("d", "s")]),
+    ("ď", &[("d", "j"), ("d", "gy"), ("t", "gy")]),
+    ("ť", &[("t", "j"), ("t", "ty"), ("d", "ty")]),
+    ("j", &[("l", "j")]),
+    ("ń", &[("n", "j"), ("ny", "j")]),
+];
+
+// The number of partial candidates kept at each position:
+const BEAM: usize = 256;
+
+// Whether the segment at position `i`, boundaries skipped,
+// is an obstruent triggering voice assimilation:
+fn before_obstruent( segments: &[String], i: usize ) -> bool
+{
+    match segments[i..].iter().find( | s | ! "+|#".contains( s.as_str() ) )
+    {
+        Some( s ) => inventory::triggers_voicing( s ),
+        None => false
+    }
+}
+
+// Spellings of the segments starting at position `i`, with the
+// number of segments they cover and their cost:
+fn alternatives( segments: &[String], i: usize ) -> Vec<(String, usize, usize)>
+{
+    let mut result = Vec::new();
+    for ( pattern, spellings ) in PATTERNS.iter()
+    {
+        let n = pattern.len();
+        if i + n <= segments.len() &&
+            pattern.iter().zip( &segments[i..i + n] ).all( | (p, s) | p == s )
+        {
+            for ( cost, spelling ) in spellings.iter().enumerate()
+            {
+                result.push( ( spelling.to_string(), n, cost ) );
+            }
+        }
+    }
+    let segment = segments[i].as_str();
+    if i + 2 < segments.len() && "+|#".contains( segments[i + 1].as_str() ) &&
+        segments[i + 2] == segment
+    {
+        let boundary = if segments[i + 1] == "#" { " " } else { &segments[i + 1] };
+        for ( s, pairs ) in SPLITS.iter()
+        {
+            if *s == segment
+            {
+                for ( before, after ) in pairs.iter()
+                {
+                    result.push( ( format!( "{}{}{}", before, boundary, after ), 3, 0 ) );
+                }
+            }
+        }
+    }
+    if before_obstruent( segments, i + 1 )
+    {
+        for ( s, counterpart ) in COUNTERPARTS.iter()
+        {
+            if *s == segment
+            {
+                result.push( ( counterpart.to_string(), 1, 2 ) );
+            }
+        }
+    }
+    // a word-final vowel may stand for vowel + deleted /h/:
+    if inventory::is_vowel( segment ) &&
+        ( i + 1 == segments.len() || segments[i + 1] == "|" ||
+          segments[i + 1] == "#" )
+    {
+        result.push( ( format!( "{}h", segment ), 1, 3 ) );
+    }
+    result
+}
+
+/// Orthographic candidates of `segments` with their costs,
+/// cheapest first.
+pub fn candidates( segments: &[String] ) -> Result<Vec<(String, usize)>, String>
+{
+    // partial candidates ending at each position:
+    let mut partial: Map<usize, Vec<(String, usize)>> = Map::new();
+    partial.insert( 0, vec![( String::new(), 0 )] );
+    for i in 0..segments.len()
+    {
+        let mut here = match partial.remove( &i )
+        {
+            Some( here ) => here,
+            None => { continue; }
+        };
+        here.sort_by_key( | (_, cost) | *cost );
+        here.truncate( BEAM );
+        let next = alternatives( segments, i );
+        if next.is_empty()
+        {
+            return Err( format!( "No spelling for segment {}", &segments[i] ) );
+        }
+        for ( spelling, length, cost ) in next
+        {
+            let continued = partial.entry( i + length ).or_default();
+            for ( prefix, prefix_cost ) in &here
+            {
+                continued.push(
+                    ( format!( "{}{}", prefix, spelling ), prefix_cost + cost ) );
+            }
+        }
+    }
+    let mut result = partial.remove( &segments.len() ).unwrap_or_default();
+    result.sort_by( | a, b | a.1.cmp( &b.1 ).then( a.0.cmp( &b.0 ) ) );
+    Ok( result )
+}
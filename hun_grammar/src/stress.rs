@@ -0,0 +1,180 @@
+use std::ops::Range;
+
+use crate::tabular::table::{Table, Tier};
+
+use crate::ipa;
+use crate::syllable;
+
+// Word stress of converted tables.  The first syllable of a word
+// has primary stress, the first syllable of each further member
+// of a compound secondary stress (kis|szék).  Secondary stresses
+// fall on every second syllable after a stress, unless that
+// syllable is light and the next one is heavy, which is stressed
+// instead (a syllable is heavy if its vowel is long or it has a
+// coda).  Words marked as unstressed (clitics, articles) have
+// no stress at all.
+
+/// Words unstressed in a phrase when clitics are de-stressed.
+pub const CLITICS: [&str; 3] = ["a", "az", "egy"];
+
+struct Syllable
+{
+    nucleus: Range<usize>,
+    heavy: bool,
+    word: usize,
+    // the first syllable of a word or a compound member:
+    initial: bool,
+}
+
+fn syllables( table: &Table, tier_names: &[&str] ) -> Result<Vec<Syllable>, String>
+{
+    // the columns of word and compound boundaries:
+    let mut words = Vec::new();
+    let mut compounds = Vec::new();
+    for columns in ipa::segments( table )
+    {
+        match ipa::features( table, tier_names, &columns ).boundary.first()
+        {
+            Some( &"word" ) => words.push( columns.start ),
+            Some( &"compound" ) => compounds.push( columns.start ),
+            _ => ()
+        }
+    }
+    let mut result: Vec<Syllable> = Vec::new();
+    let mut previous_end = 0;
+    for constituent in syllable::constituents( table, tier_names )?
+    {
+        let start = constituent.columns.start;
+        if constituent.syllable == result.len()
+        {
+            let word = words.iter().filter( | w | **w < start ).count();
+            let initial =
+                result.last().is_none_or( | last | last.word != word ) ||
+                compounds.iter().any( | c | previous_end <= *c && *c < start );
+            result.push( Syllable { nucleus: 0..0, heavy: false, word, initial } );
+        }
+        let current = result.last_mut().unwrap();
+        match constituent.role
+        {
+            "nucleus" =>
+            {
+                current.nucleus = constituent.columns.clone();
+                current.heavy |=
+                    ipa::features( table, tier_names, &constituent.columns ).
+                    timing_slots > 1;
+            }
+            "coda" => { current.heavy = true; }
+            _ => ()
+        }
+        previous_end = constituent.columns.end;
+    }
+    Ok( result )
+}
+
+/// The stress tier of `table`: "primary", "secondary" or
+/// "unstressed" over the syllable nuclei, gaps elsewhere.
+/// `unstressed_words` flags the words (counted from 0) without
+/// stress, missing words being stressed.
+pub fn stress_tier( table: &Table, tier_names: &[&str], unstressed_words: &[bool] )
+    -> Result<Tier<'static>, String>
+{
+    let syllables = syllables( table, tier_names )?;
+    let mut stresses: Vec<&'static str> = Vec::new();
+    // the last stressed syllable of the current word:
+    let mut last: Option<usize> = None;
+    for ( i, syllable ) in syllables.iter().enumerate()
+    {
+        if syllable.initial && ( i == 0 || syllables[i - 1].word != syllable.word )
+        {
+            last = None;
+        }
+        let stress =
+            if unstressed_words.get( syllable.word ) == Some( &true ) { "unstressed" }
+            else if syllable.initial
+            {
+                if last.is_none() { "primary" } else { "secondary" }
+            }
+            else
+            {
+                let next = syllables.get( i + 1 ).
+                    filter( | next | next.word == syllable.word && ! next.initial );
+                match last.map( | l | i - l )
+                {
+                    Some( 2 ) if ! syllable.heavy &&
+                        next.is_some_and( | next | next.heavy ) => "unstressed",
+                    Some( 2 ) | Some( 3 ) => "secondary",
+                    _ => "unstressed"
+                }
+            };
+        if stress != "unstressed"
+        {
+            last = Some( i );
+        }
+        stresses.push( stress );
+    }
+
+    let mut slots = Vec::new();
+    for columns in ipa::segments( table )
+    {
+        match syllables.iter().position( | s | s.nucleus == columns )
+        {
+            Some( i ) =>
+                slots.extend( Tier::new_slot( stresses[i], columns.len() ).slots ),
+            None => slots.extend( Tier::new_gap( columns.len() ).slots )
+        }
+    }
+    Ok( Tier { slots } )
+}
+
+#[cfg(test)]
+mod tests
+{
+    use crate::hungarian_grapheme::HungarianTable;
+    use crate::tabular::grapheme::GraphemeTabular;
+    use crate::tabular::table::Table;
+
+    // The stresses of the syllables of a stressed table:
+    fn stresses( table: &Table<'static> ) -> Vec<&'static str>
+    {
+        let converter = HungarianTable::new();
+        let tier = converter.tier_name_to_tier_index( &"stress" );
+        table.tiers[tier].slots.iter().
+            filter( | slot | slot.is_left_aligned() ).
+            filter_map( | slot | slot.element() ).
+            collect()
+    }
+
+    fn word( word: &'static str ) -> Vec<&'static str>
+    {
+        let converter = HungarianTable::new();
+        let table = converter.from_string( word ).unwrap();
+        stresses( &converter.stress( &table, &[] ).unwrap() )
+    }
+
+    #[test]
+    fn every_second_syllable_is_stressed()
+    {
+        assert_eq!( word( "alma" ), ["primary", "unstressed"] );
+        assert_eq!( word( "katona" ), ["primary", "unstressed", "secondary"] );
+        assert_eq!( word( "szerencsétlenség" ),
+                    ["primary", "unstressed", "secondary", "unstressed", "secondary"] );
+    }
+
+    #[test]
+    fn a_light_syllable_before_a_heavy_one_is_skipped()
+    {
+        assert_eq!( word( "paradicsom" ),
+                    ["primary", "unstressed", "unstressed", "secondary"] );
+    }
+
+    #[test]
+    fn compound_members_and_clitics()
+    {
+        assert_eq!( word( "kis|szék" ), ["primary", "secondary"] );
+        let converter = HungarianTable::new();
+        let phrase = converter.from_phrase_stressed( "a ház", true ).unwrap();
+        assert_eq!( stresses( &phrase ), ["unstressed", "primary"] );
+        let phrase = converter.from_phrase_stressed( "a ház", false ).unwrap();
+        assert_eq!( stresses( &phrase ), ["primary", "primary"] );
+    }
+}
@@ -0,0 +1,174 @@
+use crate::harmony::HarmonyClass;
+use crate::inventory::{self, is_vowel};
+
+// Harmonic suffixes and the processes applying at the seam of a
+// stem and a suffix, on segments in the notation of
+// `HungarianTable::from_grapheme`.  Suffix templates are spelled
+// with the harmonic archiphonemes `A` (a/e), `Á` (á/é), `O`
+// (o/e/ö), `Ó` (ó/ő), `U` (u/ü) and `Ú` (ú/ű): -nAk, -hOz, -bAn,
+// -tÓl, -vAl.  At the seam,
+// - a stem-final short low vowel is lengthened (alma+ban -> almában),
+// - a suffix-initial /v/ is assimilated to a stem-final consonant,
+//   forming a geminate unless it follows a consonant
+//   (kéz+vel -> kéz+zel, kert+vel -> kert+el),
+// - a stem-final geminate before a consonant is shortened
+//   (ott+ról -> otról),
+// - a stem-final obstruent takes the voicing of a suffix-initial
+//   obstruent other than /v/ (kéz+től -> késtől, kút+ba -> kúdba),
+// - identical consonants form a geminate, short after a consonant
+//   (kút+tól, kert+től -> kert+ől).
+// The boundary stays between the two halves of a geminate, as in
+// the tables of `from_string` (kéz+zel, not kéZ+el).
+
+// Archiphonemes and their back, front unrounded and
+// front rounded realizations:
+const ARCHIPHONEMES: [(char, [char; 3]); 6] = [
+    ('A', ['a', 'e', 'e']), ('Á', ['á', 'é', 'é']),
+    ('O', ['o', 'e', 'ö']), ('Ó', ['ó', 'ő', 'ő']),
+    ('U', ['u', 'ü', 'ü']), ('Ú', ['ú', 'ű', 'ű']),
+];
+
+// Consonants without a geminate:
+const NO_GEMINATE: [&str; 4] = ["h", "x", "ɣ", "ɦ"];
+
+/// The allomorph of a suffix template (with or without a
+/// leading `-`) after a stem of harmony class `class`, which
+/// must not be `Mixed`.
+pub fn allomorph( template: &str, class: HarmonyClass ) -> Result<String, String>
+{
+    let index =
+        match class
+        {
+            HarmonyClass::Back => 0,
+            HarmonyClass::FrontUnrounded => 1,
+            HarmonyClass::FrontRounded => 2,
+            HarmonyClass::Mixed =>
+            {
+                return Err( "No allomorph for a mixed stem".to_string() );
+            }
+        };
+    Ok( template.trim_start_matches( '-' ).chars().
+        map( | c |
+             match ARCHIPHONEMES.iter().find( | (a, _) | *a == c )
+             {
+                 Some( (_, realizations) ) => realizations[index],
+                 None => c
+             } ).
+        collect() )
+}
+
+fn is_geminate( segment: &str ) -> bool
+{
+    segment.to_lowercase() != segment
+}
+
+/// The segments of `stem` and `suffix` joined by a morpheme
+/// boundary, with the seam processes applied.
+pub fn attach( stem: &[String], suffix: &[String] ) -> Vec<String>
+{
+    let mut stem = stem.to_vec();
+    let mut suffix = suffix.to_vec();
+    let last = stem.len().wrapping_sub( 1 );
+    match stem.last().map( String::as_str )
+    {
+        Some( "a" ) => { stem[last] = "á".to_string(); }
+        Some( "e" ) => { stem[last] = "é".to_string(); }
+        Some( final_segment ) if ! is_vowel( final_segment ) =>
+        {
+            let final_segment = final_segment.to_string();
+            let after_consonant = last > 0 && ! is_vowel( &stem[last - 1] );
+            match suffix.first().map( String::as_str )
+            {
+                Some( "v" ) =>
+                {
+                    if ! after_consonant && ! NO_GEMINATE.contains( &final_segment.as_str() )
+                    {
+                        stem[last] = final_segment.to_lowercase();
+                        suffix[0] = final_segment.to_lowercase();
+                    }
+                    else
+                    {
+                        suffix.remove( 0 );
+                    }
+                }
+                Some( initial ) if ! is_vowel( initial ) =>
+                {
+                    if is_geminate( &final_segment )
+                    {
+                        stem[last] = final_segment.to_lowercase();
+                    }
+                    if let ( true, Some( voiced ) ) =
+                        ( inventory::triggers_voicing( initial ),
+                          inventory::voicing( initial ) )
+                    {
+                        if let Some( assimilated ) =
+                            inventory::with_voicing( &stem[last], voiced )
+                        {
+                            stem[last] = assimilated.to_string();
+                        }
+                    }
+                    // identical consonants form a geminate, short
+                    // after a consonant:
+                    if stem[last] == suffix[0] && after_consonant
+                    {
+                        suffix.remove( 0 );
+                    }
+                }
+                _ => ()
+            }
+        }
+        _ => ()
+    }
+    stem.push( "+".to_string() );
+    stem.extend( suffix );
+    stem
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::{allomorph, attach};
+    use crate::harmony::HarmonyClass;
+
+    fn attached( stem: &[&str], suffix: &[&str] ) -> String
+    {
+        let stem: Vec<String> = stem.iter().map( | s | s.to_string() ).collect();
+        let suffix: Vec<String> = suffix.iter().map( | s | s.to_string() ).collect();
+        attach( &stem, &suffix ).concat()
+    }
+
+    #[test]
+    fn allomorphs_follow_the_harmony_class()
+    {
+        assert_eq!( allomorph( "-hOz", HarmonyClass::Back ).unwrap(), "hoz" );
+        assert_eq!( allomorph( "-hOz", HarmonyClass::FrontUnrounded ).unwrap(), "hez" );
+        assert_eq!( allomorph( "-hOz", HarmonyClass::FrontRounded ).unwrap(), "höz" );
+        assert_eq!( allomorph( "tÓl", HarmonyClass::FrontRounded ).unwrap(), "től" );
+        assert!( allomorph( "-nAk", HarmonyClass::Mixed ).is_err() );
+    }
+
+    #[test]
+    fn seam_processes_apply()
+    {
+        assert_eq!( attached( &["a", "l", "m", "a"], &["b", "a", "n"] ), "almá+ban" );
+        assert_eq!( attached( &["k", "é", "z"], &["v", "e", "l"] ), "kéz+zel" );
+        assert_eq!( attached( &["k", "e", "r", "t"], &["v", "e", "l"] ), "kert+el" );
+        assert_eq!( attached( &["t", "o", "L"], &["v", "a", "l"] ), "tol+lal" );
+        assert_eq!( attached( &["o", "T"], &["r", "ó", "l"] ), "ot+ról" );
+        assert_eq!( attached( &["k", "é", "z"], &["t", "ő", "l"] ), "kés+től" );
+        assert_eq!( attached( &["k", "ú", "t"], &["b", "a"] ), "kúd+ba" );
+        assert_eq!( attached( &["k", "ú", "t"], &["t", "ó", "l"] ), "kút+tól" );
+        assert_eq!( attached( &["k", "e", "r", "t"], &["t", "ő", "l"] ), "kert+ől" );
+    }
+
+    #[test]
+    fn fricatives_without_a_geminate_assimilate_in_voice_only()
+    {
+        // Bach: /x/ and /h/ take part in voicing, /h/ does not trigger it:
+        assert_eq!( attached( &["b", "a", "x"], &["b", "a", "n"] ), "baɣ+ban" );
+        assert_eq!( attached( &["b", "a", "x"], &["h", "o", "z"] ), "bax+hoz" );
+        assert_eq!( attached( &["b", "a", "x"], &["v", "a", "l"] ), "bax+al" );
+        assert_eq!( attached( &["m", "é", "h"], &["b", "e", "n"] ), "méɦ+ben" );
+        assert_eq!( attached( &["k", "é", "z"], &["h", "e", "z"] ), "kéz+hez" );
+    }
+}
@@ -0,0 +1,251 @@
+use std::ops::Range;
+
+use crate::tabular::table::{Table, Tier};
+
+use crate::ipa;
+
+// Syllabification of converted tables.  Segments are found as in
+// `ipa` (vowels have an open airflow).  Compound and word
+// boundaries delimit syllabification domains, morpheme boundaries
+// do not (ház+am -> há.zam).  In a domain, consonants before the
+// first vowel are onsets, consonants after the last one codas;
+// between two vowels the last consonant is the onset of the second
+// syllable and the others are codas (al.ma, temp.lom).  A geminate
+// in that position is split: its first timing slot is a coda,
+// its second one an onset (it.tas).  Consonants of a domain
+// without a vowel are left unsyllabified.
+
+/// A slot of the syllable tier: its columns, its role ("onset",
+/// "nucleus" or "coda"), and the number of its syllable.
+pub struct Constituent
+{
+    pub columns: Range<usize>,
+    pub role: &'static str,
+    pub syllable: usize,
+}
+
+enum Kind
+{
+    Boundary( bool ),           // delimiting a domain
+    Vowel,
+    // the columns of the timing slots:
+    Consonant( Vec<Range<usize>> ),
+}
+
+/// The columns of the non-empty slots of `tier` within `columns`.
+pub fn timing_slots( tier: &Tier, columns: &Range<usize> ) -> Vec<Range<usize>>
+{
+    let mut result = Vec::new();
+    let mut start = columns.start;
+    for column in columns.clone()
+    {
+        if tier.slots[column].is_right_aligned() || column + 1 == columns.end
+        {
+            if ! tier.slots[column].is_gap()
+            {
+                result.push( start..column + 1 );
+            }
+            start = column + 1;
+        }
+    }
+    result
+}
+
+/// The syllabic constituents of `table`, in the order of its columns.
+pub fn constituents( table: &Table, tier_names: &[&str] )
+    -> Result<Vec<Constituent>, String>
+{
+    let time =
+        tier_names.iter().position( | n | *n == "time" ).
+        ok_or_else( || "No time tier".to_string() )?;
+    let mut segments: Vec<(Range<usize>, Kind)> = Vec::new();
+    for columns in ipa::segments( table )
+    {
+        let features = ipa::features( table, tier_names, &columns );
+        let kind =
+            if let Some( boundary ) = features.boundary.first()
+            {
+                Kind::Boundary( *boundary != "morpheme" )
+            }
+            else if features.airflow == ["open"] { Kind::Vowel }
+            else
+            {
+                Kind::Consonant( timing_slots( &table.tiers[time], &columns ) )
+            };
+        segments.push( ( columns, kind ) );
+    }
+
+    let mut result = Vec::new();
+    let mut syllable = 0;
+    for domain in segments.split( | (_, kind) |
+                                   matches!( kind, Kind::Boundary( true ) ) )
+    {
+        let vowels: Vec<usize> =
+            domain.iter().enumerate().
+            filter( | (_, (_, kind)) | matches!( kind, Kind::Vowel ) ).
+            map( | (i, _) | i ).
+            collect();
+        let ( first, last ) =
+            match ( vowels.first(), vowels.last() )
+            {
+                ( Some( first ), Some( last ) ) => ( *first, *last ),
+                _ => { continue; }
+            };
+        // the syllable of each vowel, counted in the domain:
+        let mut nucleus = 0;
+        for ( i, ( columns, kind ) ) in domain.iter().enumerate()
+        {
+            let slots =
+                match kind
+                {
+                    Kind::Boundary( _ ) => { continue; }
+                    Kind::Vowel =>
+                    {
+                        result.push( Constituent
+                                     {
+                                         columns: columns.clone(),
+                                         role: "nucleus",
+                                         syllable: syllable + nucleus,
+                                     } );
+                        nucleus += 1;
+                        continue;
+                    }
+                    Kind::Consonant( slots ) => slots
+                };
+            if i < first
+            {
+                result.push( Constituent
+                             { columns: columns.clone(), role: "onset",
+                               syllable } );
+            }
+            else if i > last
+            {
+                result.push( Constituent
+                             { columns: columns.clone(), role: "coda",
+                               syllable: syllable + nucleus - 1 } );
+            }
+            else
+            {
+                // the last consonant before the next vowel:
+                let onset =
+                    domain[i + 1..].iter().
+                    take_while( | (_, kind) | ! matches!( kind, Kind::Vowel ) ).
+                    all( | (_, kind) | matches!( kind, Kind::Boundary( _ ) ) );
+                if ! onset
+                {
+                    result.push( Constituent
+                                 { columns: columns.clone(), role: "coda",
+                                   syllable: syllable + nucleus - 1 } );
+                }
+                else if slots.len() > 1
+                {
+                    result.push( Constituent
+                                 { columns: columns.start..slots[0].end,
+                                   role: "coda",
+                                   syllable: syllable + nucleus - 1 } );
+                    result.push( Constituent
+                                 { columns: slots[0].end..columns.end,
+                                   role: "onset",
+                                   syllable: syllable + nucleus } );
+                }
+                else
+                {
+                    result.push( Constituent
+                                 { columns: columns.clone(), role: "onset",
+                                   syllable: syllable + nucleus } );
+                }
+            }
+        }
+        syllable += nucleus;
+    }
+    Ok( result )
+}
+
+/// The syllable tier of `table`: its constituents, with gaps
+/// over boundaries and unsyllabified consonants.
+pub fn syllable_tier( table: &Table, tier_names: &[&str] )
+    -> Result<Tier<'static>, String>
+{
+    let constituents = constituents( table, tier_names )?;
+    let mut slots = Vec::new();
+    for columns in ipa::segments( table )
+    {
+        let within: Vec<&Constituent> =
+            constituents.iter().
+            filter( | c | columns.contains( &c.columns.start ) ).
+            collect();
+        if within.is_empty()
+        {
+            slots.extend( Tier::new_gap( columns.len() ).slots );
+        }
+        for constituent in within
+        {
+            slots.extend(
+                Tier::new_slot( constituent.role,
+                                constituent.columns.len() ).slots );
+        }
+    }
+    Ok( Tier { slots } )
+}
+
+/// The column ranges of the syllables of `table`.
+pub fn syllables( table: &Table, tier_names: &[&str] )
+    -> Result<Vec<Range<usize>>, String>
+{
+    let mut result: Vec<Range<usize>> = Vec::new();
+    for constituent in constituents( table, tier_names )?
+    {
+        match result.get_mut( constituent.syllable )
+        {
+            Some( columns ) => { columns.end = constituent.columns.end; }
+            None => result.push( constituent.columns )
+        }
+    }
+    Ok( result )
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::constituents;
+    use crate::hungarian_grapheme::HungarianTable;
+    use crate::tabular::grapheme::GraphemeTabular;
+
+    // The syllables of `word` as the IPA of their segments:
+    fn syllables( word: &'static str ) -> Vec<String>
+    {
+        let converter = HungarianTable::new();
+        let table = converter.from_string( word ).unwrap();
+        converter.syllables( &table ).unwrap().into_iter().
+            map( | columns | converter.to_ipa( &table.columns( columns.start, columns.end ),
+                                              crate::ipa::IpaStyle::Broad ).unwrap() ).
+            collect()
+    }
+
+    #[test]
+    fn consonants_between_vowels_are_split()
+    {
+        assert_eq!( syllables( "alma" ), ["ɒl", "mɒ"] );
+        assert_eq!( syllables( "templom" ), ["tɛmp", "lom"] );
+        assert_eq!( syllables( "strand" ), ["ʃtrɒnd"] );
+    }
+
+    #[test]
+    fn boundaries_delimit_domains()
+    {
+        // a morpheme boundary does not, a compound boundary does:
+        assert_eq!( syllables( "ház+am" ), ["haː", "zɒm"] );
+        assert_eq!( syllables( "kar|óra" ), ["kɒr", "oː", "rɒ"] );
+    }
+
+    #[test]
+    fn geminates_are_split()
+    {
+        let converter = HungarianTable::new();
+        let table = converter.from_string( "ittas" ).unwrap();
+        let roles: Vec<&str> =
+            constituents( &table, &converter.all_tier_names() ).unwrap().
+            into_iter().map( | c | c.role ).collect();
+        assert_eq!( roles, ["nucleus", "coda", "onset", "nucleus", "coda"] );
+    }
+}
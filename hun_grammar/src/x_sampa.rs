@@ -0,0 +1,128 @@
+// Conversion between X-SAMPA and IPA, restricted to the symbols
+// used for Hungarian.  Affricates are written with the tie bar `_`
+// (`t_s`, `t_S`); `ts` is a sequence of a stop and a fricative.
+
+// X-SAMPA symbols and the IPA they stand for (the longest
+// symbol matching is read):
+const SYMBOLS: [(&str, &str); 55] = [
+    // diacritics and marks
+    ("_d", "\u{32a}"), ("_h", "ʰ"), ("_0", "\u{325}"), ("_^", "\u{32f}"),
+    ("=", "\u{329}"),
+    ("_", "\u{361}"),
+    (":", "ː"), ("\"", "ˈ"), ("%", "ˌ"), (".", "."),
+    (" ", " "), ("+", "+"), ("|", "|"),
+    // consonants
+    ("J\\", "ɟ"), ("h\\", "ɦ"), ("v\\", "ʋ"), ("j\\", "ʝ"),
+    ("p", "p"), ("b", "b"), ("t", "t"), ("d", "d"), ("k", "k"), ("g", "g"),
+    ("c", "c"), ("f", "f"), ("v", "v"), ("s", "s"), ("z", "z"),
+    ("S", "ʃ"), ("Z", "ʒ"), ("C", "ç"), ("x", "x"), ("G", "ɣ"), ("h", "h"),
+    ("j", "j"),
+    ("l", "l"), ("L", "ʎ"), ("m", "m"), ("n", "n"), ("J", "ɲ"), ("N", "ŋ"),
+    ("r", "r"),
+    // vowels
+    ("Q", "ɒ"), ("A", "ɑ"), ("a", "a"), ("E", "ɛ"), ("e", "e"),
+    ("i", "i"), ("o", "o"), ("u", "u"), ("2", "ø"), ("y", "y"),
+    ("{", "æ"), ("M", "ɯ"), ("7", "ɤ"),
+];
+
+/// IPA for an X-SAMPA string, with the byte offset in `x_sampa`
+/// of each character of the result.
+pub fn x_sampa_to_ipa( x_sampa: &str ) -> Result<(String, Vec<usize>), String>
+{
+    let mut ipa = String::new();
+    let mut offsets = Vec::new();
+    let mut position = 0;
+    while position < x_sampa.len()
+    {
+        let rest = &x_sampa[position..];
+        match SYMBOLS.iter().
+            filter( | (x, _) | rest.starts_with( x ) ).
+            max_by_key( | (x, _) | x.len() )
+        {
+            Some( (x, i) ) =>
+            {
+                for c in i.chars()
+                {
+                    ipa.push( c );
+                    offsets.push( position );
+                }
+                position += x.len();
+            }
+            None =>
+            {
+                let c = rest.chars().next().unwrap();
+                return Err( format!( "Unknown X-SAMPA symbol \"{}\" at position {}",
+                                     c, position ) );
+            }
+        }
+    }
+    Ok( ( ipa, offsets ) )
+}
+
+/// X-SAMPA for an IPA string.
+pub fn ipa_to_x_sampa( ipa: &str ) -> Result<String, String>
+{
+    let mut result = String::new();
+    let mut position = 0;
+    while position < ipa.len()
+    {
+        let rest = &ipa[position..];
+        match SYMBOLS.iter().
+            filter( | (_, i) | rest.starts_with( i ) ).
+            max_by_key( | (_, i) | i.len() )
+        {
+            Some( (x, i) ) =>
+            {
+                result.push_str( x );
+                position += i.len();
+            }
+            None =>
+            {
+                let c = rest.chars().next().unwrap();
+                return Err( format!( "No X-SAMPA symbol for \"{}\" at position {}",
+                                     c, position ) );
+            }
+        }
+    }
+    Ok( result )
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::{ipa_to_x_sampa, x_sampa_to_ipa};
+
+    fn both_ways( ipa: &str, x_sampa: &str )
+    {
+        assert_eq!( ipa_to_x_sampa( ipa ).unwrap(), x_sampa );
+        assert_eq!( x_sampa_to_ipa( x_sampa ).unwrap().0, ipa );
+    }
+
+    #[test]
+    fn symbols_convert_both_ways()
+    {
+        both_ways( "t͡sukor", "t_sukor" );
+        both_ways( "hosːuː", "hos:u:" );
+        both_ways( "t̪ɒt̪ɒ", "t_dQt_dQ" );
+        // Slovak mäso, ľad, and the palatal fricatives of Hungarian
+        // kapj, hagyj:
+        both_ways( "mæso", "m{so" );
+        both_ways( "ʎat", "Lat" );
+        both_ways( "kɒpç", "kQpC" );
+        both_ways( "hɒɟʝ", "hQJ\\j\\" );
+        both_ways( "bɒŋk", "bQNk" );
+        // a Western diphthong, and Slovak syllabic liquids:
+        both_ways( "lei\u{32f}", "lei_^" );
+        both_ways( "vl\u{329}k", "vl=k" );
+        both_ways( "vr\u{329}ːba", "vr=:ba" );
+    }
+
+    #[test]
+    fn unknown_symbols_are_reported()
+    {
+        assert_eq!( ipa_to_x_sampa( "ɒθ" ).unwrap_err(),
+                    "No X-SAMPA symbol for \"θ\" at position 2" );
+        assert_eq!( x_sampa_to_ipa( "aT" ).unwrap_err(),
+                    "Unknown X-SAMPA symbol \"T\" at position 1" );
+    }
+}
@@ -0,0 +1,52 @@
+use crate::peg;
+
+peg::parser!{
+    pub grammar graphemes() for str
+    {
+        //* Break down (lowercased) Slovak character strings into
+        //* segments: the symbols of `hun_grammar` where the segments
+        //* coincide (č, ď, ť, ń, š, ž, x for "ch", ɦ for "h", s for "s"),
+        //* Slovak symbols otherwise (ä, ô, ľ, dz, dž, ia, ie, iu).
+        //* d, t, n, l are palatalized before e, i, í (deti, ticho, niť)
+        //* if `palatalize` is set.
+        pub rule input( palatalize: bool ) -> Vec<String> =
+            g: grapheme( palatalize )+ { g.concat() }
+
+        rule grapheme( palatalize: bool ) -> Vec<String> =
+            s: ( diphthong() / complex() /
+                 p: palatalized() {? if palatalize { Ok( p ) } else { Err( "hard" ) } } /
+                 simple() )
+        { vec![s] } /
+            "x" { vec!["k".to_string(), "s".to_string()] }
+
+        rule diphthong() -> String =
+            s: $( "ia" / "ie" / "iu" / "ô" ) { s.to_string() }
+
+        rule complex() -> String =
+            "ch" { "x".to_string() } /
+            s: $( "dz" / "dž" ) { s.to_string() }
+
+        rule palatalized() -> String =
+            "d" &front() { "ď".to_string() } /
+            "t" &front() { "ť".to_string() } /
+            "n" &front() { "ń".to_string() } /
+            "l" &front() { "ľ".to_string() }
+
+        rule front() =
+            "e" / "i" / "í"
+
+        rule simple() -> String =
+            "h" { "ɦ".to_string() } /
+            "ň" { "ń".to_string() } /
+            "q" { "k".to_string() } /
+            "w" { "v".to_string() } /
+            "y" { "i".to_string() } /
+            "ý" { "í".to_string() } /
+            s: $( "a" / "á" / "ä" / "b" / "c" / "č" / "d" / "ď" / "e" /
+                  "é" / "f" / "g" / "i" / "í" / "j" / "k" / "l" / "ĺ" /
+                  "ľ" / "m" / "n" / "o" / "ó" / "p" / "r" / "ŕ" / "s" /
+                  "š" / "t" / "ť" / "u" / "ú" / "v" / "z" / "ž" /
+                  "+" / "|" / "#" )
+        { s.to_string() }
+    }
+}
@@ -0,0 +1,6 @@
+extern crate tabular;
+extern crate hun_grammar;
+extern crate peg;
+pub mod slovak_grapheme;
+
+mod graphemes;
@@ -0,0 +1,341 @@
+use crate::tabular::grapheme::GraphemeTabular;
+use crate::tabular::tabular::Tabular;
+use crate::tabular::table::{Table, Tier};
+
+use crate::hun_grammar::hungarian_grapheme::HungarianTable;
+
+use crate::graphemes;
+
+/**
+A converter from Slovak orthography, implementing the
+`tabular::grapheme::GraphemeTabular` trait over the tiers of
+`HungarianTable`.  Segments that Slovak shares with Hungarian get
+the Hungarian tables; the others (a, ä, é, ó, the diphthongs ô, ia,
+ie, iu, the voiced affricates dz, dž, ľ, the syllabic liquids
+and the glide u̯) have tables of their own.  Syllabic liquids,
+short (r̩, l̩: vlk, krk, prst) or long (ĺ, ŕ), have the element
+`syllabic` on the jaw tier.  After a vowel or a syllabic liquid,
+v is the glide u̯ word-finally and before a consonant (krv -> [kr̩u̯],
+pravda -> [prau̯da]).  d, t, n, l are palatalized before e, i, í,
+except in the words of `HARD_WORDS` (ten, jeden, teda).
+Obstruents undergo regressive voice assimilation (v does not
+trigger it) and are devoiced word-finally (dub -> [dup],
+vták -> [fta:k]).  In the input, `+`, `|` and whitespace are
+morpheme, compound and word boundaries.
+*/
+pub struct SlovakTable
+{
+    hungarian: HungarianTable,
+}
+
+// Voiced obstruents and their voiceless counterparts:
+const VOICE_PAIRS: [(&str, &str); 10] = [
+    ("b", "p"), ("d", "t"), ("ď", "ť"), ("g", "k"), ("z", "s"),
+    ("ž", "š"), ("dz", "c"), ("dž", "č"), ("ɦ", "x"), ("v", "f"),
+];
+
+// Words where d, t, n, l are not palatalized before e, i, í:
+const HARD_WORDS: [&str; 14] = [
+    "ten", "tento", "tej", "tejto", "teda", "vtedy", "jeden", "jedenásť",
+    "jedenásty", "tenis", "telefón", "politika", "literatúra", "demokracia",
+];
+
+// Segments forming the nucleus of a syllable:
+const NUCLEI: [&str; 17] = [
+    "a", "á", "ä", "e", "é", "i", "í", "o", "ó", "u", "ú", "ô", "ia", "ie",
+    "iu", "ĺ", "ŕ",
+];
+
+impl GraphemeTabular<Table<'static>> for SlovakTable
+{
+    type TierName = &'static str;
+
+    fn new() -> Self
+    {
+        SlovakTable { hungarian: HungarianTable::new() }
+    }
+
+    fn tier_name_to_tier_index(
+        &self, tier_name: &Self::TierName )
+        -> usize
+    {
+        self.hungarian.tier_name_to_tier_index( tier_name )
+    }
+    fn tier_index_to_tier_name(
+        &self, tier_index: usize )
+        -> Self::TierName
+    {
+        self.hungarian.tier_index_to_tier_name( tier_index )
+    }
+    fn from_string( &self, graphemes: &'static str )
+                         -> Result<Table<'static>, String>
+    {
+        self.from_slovak( graphemes )
+    }
+}
+
+impl SlovakTable
+{
+    /// The segments of a Slovak string, after the realization
+    /// of liquids and v and voice assimilation.
+    pub fn segments( &self, graphemes: &str ) -> Result<Vec<String>, String>
+    {
+        let mut segments: Vec<String> = Vec::new();
+        for word in graphemes.split_whitespace()
+        {
+            let word = word.to_lowercase();
+            let palatalize = ! HARD_WORDS.contains( &word.as_str() );
+            match graphemes::graphemes::input( &word, palatalize )
+            {
+                Ok( word_segments ) =>
+                {
+                    if ! segments.is_empty()
+                    {
+                        segments.push( "#".to_string() );
+                    }
+                    segments.extend( word_segments );
+                }
+                Err( e ) => { return Err( format!( "Parse error: {}", &e ) ); }
+            }
+        }
+        Ok( SlovakTable::assimilate( SlovakTable::vocalize( segments ) ) )
+    }
+
+    /// The table of a Slovak string.
+    pub fn from_slovak( &self, graphemes: &str ) -> Result<Table<'static>, String>
+    {
+        let mut result: Option<Table<'static>> = None;
+        for segment in self.segments( graphemes )?
+        {
+            let table = SlovakTable::from_grapheme( &segment )?;
+            result = Some( match result
+                           {
+                               Some( previous ) =>
+                                   Tabular::concatenate( &previous, &table ),
+                               None => table
+                           } );
+        }
+        result.ok_or_else( || "Empty segment sequence".to_string() )
+    }
+
+    // The segment next to position `i` in the direction `step`
+    // within the word, morpheme and compound boundaries skipped:
+    fn neighbour( segments: &[String], i: usize, step: isize ) -> Option<&str>
+    {
+        let mut j = i as isize + step;
+        while j >= 0 && ( j as usize ) < segments.len()
+        {
+            match segments[j as usize].as_str()
+            {
+                "#" => { return None; }
+                "+" | "|" => { j += step; }
+                segment => { return Some( segment ); }
+            }
+        }
+        None
+    }
+
+    // Syllabic liquids between consonants or after a consonant
+    // word-finally, then v after a nucleus before a consonant or
+    // word-finally as the glide u̯:
+    fn vocalize( mut segments: Vec<String> ) -> Vec<String>
+    {
+        let consonant = | s: Option<&str> |
+            matches!( s, Some( s ) if ! NUCLEI.contains( &s ) );
+        for i in 0..segments.len()
+        {
+            if ( segments[i] == "r" || segments[i] == "l" ) &&
+                consonant( SlovakTable::neighbour( &segments, i, -1 ) ) &&
+                ! matches!( SlovakTable::neighbour( &segments, i, 1 ),
+                            Some( s ) if NUCLEI.contains( &s ) )
+            {
+                segments[i].push( '\u{329}' );
+            }
+        }
+        for i in 0..segments.len()
+        {
+            let after_nucleus =
+                matches!( SlovakTable::neighbour( &segments, i, -1 ),
+                          Some( s ) if NUCLEI.contains( &s ) || s.ends_with( '\u{329}' ) );
+            let next = SlovakTable::neighbour( &segments, i, 1 );
+            if segments[i] == "v" && after_nucleus &&
+                ( next.is_none() || consonant( next ) )
+            {
+                segments[i] = "u\u{32f}".to_string();
+            }
+        }
+        segments
+    }
+
+    fn is_voiced( segment: &str ) -> Option<bool>
+    {
+        if VOICE_PAIRS.iter().any( | (voiced, _) | *voiced == segment )
+        {
+            Some( true )
+        }
+        else if VOICE_PAIRS.iter().any( | (_, voiceless) | *voiceless == segment )
+        {
+            Some( false )
+        }
+        else { None }
+    }
+
+    // Regressive voice assimilation and final devoicing, from right
+    // to left; `next` is the voicing imposed by the following segment:
+    fn assimilate( mut segments: Vec<String> ) -> Vec<String>
+    {
+        let mut next = Some( false );
+        for segment in segments.iter_mut().rev()
+        {
+            match segment.as_str()
+            {
+                "#" => { next = Some( false ); continue; }
+                "+" | "|" => { continue; }
+                _ => ()
+            }
+            match ( SlovakTable::is_voiced( segment ), next )
+            {
+                ( Some( true ), Some( false ) ) =>
+                {
+                    let pair = VOICE_PAIRS.iter().find( | (v, _) | v == segment );
+                    *segment = pair.unwrap().1.to_string();
+                }
+                ( Some( false ), Some( true ) ) =>
+                {
+                    let pair = VOICE_PAIRS.iter().find( | (_, v) | v == segment );
+                    *segment = pair.unwrap().0.to_string();
+                }
+                _ => ()
+            }
+            next =
+                if *segment == "v" { None }
+                else { SlovakTable::is_voiced( segment ) };
+        }
+        segments
+    }
+
+    /// The table of a Slovak segment or boundary.
+    pub fn from_grapheme( grapheme: &str ) -> Result<Table<'static>, String>
+    {
+        let table =
+            match grapheme
+            {
+                // vowels
+                "a" => Table::from_str(
+                    "back, extra-low, unrounded, voiced, open, X, state;" ),
+                "ä" => Table::from_str(
+                    "front, extra-low, unrounded, voiced, open, X, state;" ),
+                "é" => Table::from_str(
+                    "2 front, 2 low, 2 unrounded, 2 voiced, 2 open, X X, 2 state;" ),
+                "ó" => Table::from_str(
+                    "2 back, 2 mid, 2 rounded, 2 voiced, 2 open, X X, 2 state;" ),
+                // diphthongs
+                "ô" => Table::from_str(
+                    "2 back, high mid, 2 rounded, 2 voiced, 2 open, X X, 2 state;" ),
+                "ia" => Table::from_str(
+                    "front back, high extra-low, 2 unrounded, 2 voiced, 2 open, X X, 2 state;" ),
+                "ie" => Table::from_str(
+                    "2 front, high low, 2 unrounded, 2 voiced, 2 open, X X, 2 state;" ),
+                "iu" => Table::from_str(
+                    "front back, 2 high, unrounded rounded, 2 voiced, 2 open, X X, 2 state;" ),
+                // voiced affricates
+                "dz" => Table::from_str(
+                    "2 dentialveolar, 2 _, 2 _, 2 voiced, stop slit, 2 X, state event;" ),
+                "dž" => Table::from_str(
+                    "2 coronal, 2 _, 2 _, 2 voiced, stop slit, 2 X, state event;" ),
+                // liquids and glides
+                "ľ" => Table::from_str(
+                    "palatal, _, _, voiced, lateral, X, state;" ),
+                "l\u{329}" => Table::from_str(
+                    "dentialveolar, syllabic, _, voiced, lateral, X, state;" ),
+                "r\u{329}" => Table::from_str(
+                    "dentialveolar, syllabic, _, voiced, trill, X, state;" ),
+                "ĺ" => Table::from_str(
+                    "2 dentialveolar, 2 syllabic, 2 _, 2 voiced, 2 lateral, X X, 2 state;" ),
+                "ŕ" => Table::from_str(
+                    "2 dentialveolar, 2 syllabic, 2 _, 2 voiced, 2 trill, X X, 2 state;" ),
+                "u\u{32f}" => Table::from_str(
+                    "back, high, rounded, voiced, approximate, X, state;" ),
+                _ => { return HungarianTable::from_grapheme( grapheme ); }
+            };
+        let mut table = table?;
+        let width = table.number_of_columns();
+        table.tiers.push( Tier::new_gap( width ) );
+        Ok( table )
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::SlovakTable;
+    use crate::tabular::grapheme::GraphemeTabular;
+    use crate::hun_grammar::ipa::{self, IpaStyle};
+    use crate::hun_grammar::x_sampa;
+
+    fn segments( s: &str ) -> String
+    {
+        SlovakTable::new().segments( s ).unwrap().join( " " )
+    }
+
+    fn ipa( s: &str ) -> String
+    {
+        let converter = SlovakTable::new();
+        let table = converter.from_slovak( s ).unwrap();
+        let tier_names: Vec<&str> =
+            ( 0..table.tiers.len() ).
+            map( | i | converter.tier_index_to_tier_name( i ) ).
+            collect();
+        ipa::table_to_ipa( &table, &tier_names, IpaStyle::Broad ).unwrap()
+    }
+
+    #[test]
+    fn liquids_between_consonants_are_syllabic()
+    {
+        assert_eq!( segments( "vlk" ), "v l\u{329} k" );
+        assert_eq!( segments( "krk" ), "k r\u{329} k" );
+        assert_eq!( segments( "prst" ), "p r\u{329} s t" );
+        assert_eq!( segments( "vlak" ), "v l a k" );
+        assert_eq!( ipa( "krk" ), "kr\u{329}k" );
+        assert_eq!( ipa( "vŕba" ), "vr\u{329}ːba" );
+    }
+
+    #[test]
+    fn syllabic_liquids_have_x_sampa()
+    {
+        for ( word, expected ) in [( "vlk", "vl=k" ), ( "prst", "pr=st" ),
+                                   ( "vŕba", "vr=:ba" )]
+        {
+            let x_sampa = x_sampa::ipa_to_x_sampa( &ipa( word ) ).unwrap();
+            assert_eq!( x_sampa, expected );
+            assert_eq!( x_sampa::x_sampa_to_ipa( &x_sampa ).unwrap().0, ipa( word ) );
+        }
+    }
+
+    #[test]
+    fn v_is_a_glide_after_a_nucleus()
+    {
+        assert_eq!( segments( "krv" ), "k r\u{329} u\u{32f}" );
+        assert_eq!( segments( "pravda" ), "p r a u\u{32f} d a" );
+        assert_eq!( segments( "voda" ), "v o d a" );
+        assert_eq!( segments( "vták" ), "f t á k" );
+        assert_eq!( ipa( "pravda" ), "prau\u{32f}da" );
+    }
+
+    #[test]
+    fn some_words_are_not_palatalized()
+    {
+        assert_eq!( segments( "deti" ), "ď e ť i" );
+        assert_eq!( segments( "niť" ), "ń i ť" );
+        assert_eq!( segments( "ten" ), "t e n" );
+        assert_eq!( segments( "jeden deň" ), "j e d e n # ď e ń" );
+        assert_eq!( segments( "teda" ), "t e d a" );
+    }
+
+    #[test]
+    fn obstruents_assimilate_in_voice()
+    {
+        assert_eq!( segments( "dub" ), "d u p" );
+        assert_eq!( segments( "kde" ), "g ď e" );
+    }
+}
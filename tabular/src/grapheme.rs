@@ -1,6 +1,6 @@
 use crate::tabular::Tabular;
 
-/**!
+/**
 A converter of grapheme sequences to tabular representation
 in a given language must implement this trait.
 */
@@ -15,6 +15,8 @@ pub trait GraphemeTabular<Tab: Tabular>
     fn tier_index_to_tier_name(
         &self, tier_index: usize )
         -> Self::TierName;
+    // the name is kept for the converters implementing the trait:
+    #[allow( clippy::wrong_self_convention )]
     fn from_string( &self, graphemes: &'static str )
                      -> Result<Tab, String>;
 }
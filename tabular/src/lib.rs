@@ -3,6 +3,7 @@ extern crate peg;
 pub mod tabular;
 pub mod grapheme;
 pub mod table;
+pub mod spec;
 mod table_grammar;
 mod label;
 mod grammar;
@@ -0,0 +1,598 @@
+use crate::grapheme::GraphemeTabular;
+use crate::tabular::Tabular;
+use crate::table::{Table, Tier};
+
+/**
+A converter driven by a declarative orthography specification,
+loaded at runtime, implementing the `grapheme::GraphemeTabular`
+trait.  A specification consists of lines (`//` starts a comment):
+
+```text
+tiers tongue jaw lips voice airflow time eventuality boundary
+segment a = back, low, rounded, voiced, open, X, state
+segment č = 2 coronal, 2 _, 2 _, 2 voiceless, stop slit, 2 X, state event
+grapheme cs = č
+grapheme ccs = č č
+class voiced = b d g
+class voiceless = p t k
+stage voice repeat
+rule {voiced} -> {voiceless} / _ {voiceless}
+rule t s -> c
+rule h -> 0 / {vowel} _ #
+```
+
+The input is cut into graphemes (the longest one matching is read,
+segment names standing for themselves), whitespace being a word
+boundary (`#`).  The segments are then rewritten by the stages in
+order.  A rule rewrites a sequence of segments or classes into
+another (`0`: nothing), optionally in a context (`#` matching
+a word edge); a class in the result stands for the member with the
+index of the segment matched by the class at the same position.
+At each position the first matching rule applies, contexts being
+checked on the input of the stage; a `repeat` stage is applied
+until the segments do not change.
+If there is a tier named "boundary", segments are defined without
+it and get a gap there; a word boundary is then a column of its own,
+empty on all tiers except "boundary" where it is `word`, as in
+`HungarianTable`.  Without it, word boundaries leave no trace in
+the table.
+*/
+pub struct SpecTable<'a>
+{
+    tier_names: Vec<&'a str>,
+    segments: Vec<(&'a str, Table<'a>)>,
+    graphemes: Vec<(&'a str, Vec<&'a str>)>,
+    classes: Vec<(&'a str, Vec<&'a str>)>,
+    stages: Vec<Stage<'a>>,
+}
+
+// An ordered set of rules:
+struct Stage<'a>
+{
+    repeat: bool,
+    rules: Vec<Rule<'a>>,
+}
+
+struct Rule<'a>
+{
+    target: Vec<Term<'a>>,
+    replacement: Vec<Term<'a>>,
+    left: Vec<Term<'a>>,
+    right: Vec<Term<'a>>,
+}
+
+#[derive( PartialEq, Eq, Clone, Copy, Debug )]
+enum Term<'a>
+{
+    Segment( &'a str ),
+    Class( &'a str ),
+    Edge,
+}
+
+// Applications of a `repeat` stage before giving up:
+const MAX_REPETITIONS: usize = 100;
+
+impl<'a> GraphemeTabular<Table<'a>> for SpecTable<'a>
+{
+    type TierName = &'a str;
+
+    /// An empty specification, to be filled by `load`.
+    fn new() -> Self
+    {
+        SpecTable
+        {
+            tier_names: Vec::new(),
+            segments: Vec::new(),
+            graphemes: Vec::new(),
+            classes: Vec::new(),
+            stages: Vec::new(),
+        }
+    }
+
+    fn tier_name_to_tier_index(
+        &self, tier_name: &Self::TierName )
+        -> usize
+    {
+        <[_]>::iter( &self.tier_names ).
+            position( | name | name == tier_name ).unwrap()
+    }
+    fn tier_index_to_tier_name(
+        &self, tier_index: usize )
+        -> Self::TierName
+    {
+        self.tier_names[tier_index]
+    }
+    fn from_string( &self, graphemes: &'static str )
+                         -> Result<Table<'a>, String>
+    {
+        self.convert( graphemes )
+    }
+}
+
+impl<'a> SpecTable<'a>
+{
+    /// A converter for the specification `spec`.
+    pub fn from_spec( spec: &'a str ) -> Result<Self, String>
+    {
+        let mut result = SpecTable::new();
+        result.load( spec )?;
+        Ok( result )
+    }
+
+    /// Add the definitions of `spec` to the specification.
+    pub fn load( &mut self, spec: &'a str ) -> Result<(), String>
+    {
+        for ( number, line ) in spec.lines().enumerate()
+        {
+            let line = match line.find( "//" )
+            {
+                Some( i ) => &line[..i],
+                None => line
+            };
+            let line = line.trim();
+            if line.is_empty()
+            {
+                continue;
+            }
+            self.load_line( line ).
+                map_err( | e | format!( "line {}: {}", number + 1, e ) )?;
+        }
+        Ok( () )
+    }
+
+    fn load_line( &mut self, line: &'a str ) -> Result<(), String>
+    {
+        let ( keyword, rest ) = match line.find( char::is_whitespace )
+        {
+            Some( i ) => ( &line[..i], line[i..].trim() ),
+            None => ( line, "" )
+        };
+        match keyword
+        {
+            "tiers" =>
+            {
+                self.tier_names = rest.split_whitespace().collect();
+            }
+            "segment" =>
+            {
+                let ( name, definition ) = SpecTable::definition( rest )?;
+                let mut table = Table::parse( definition )?;
+                let boundary = self.boundary_tier();
+                let tiers =
+                    self.tier_names.len() - if boundary.is_some() { 1 } else { 0 };
+                if table.tiers.len() != tiers
+                {
+                    return Err( format!( "segment {} has {} tiers instead of {}",
+                                         name, table.tiers.len(), tiers ) );
+                }
+                if let Some( index ) = boundary
+                {
+                    let width = table.number_of_columns();
+                    table.tiers.insert( index, Tier::new_gap( width ) );
+                }
+                self.segments.push( ( name, table ) );
+            }
+            "grapheme" =>
+            {
+                let ( name, definition ) = SpecTable::definition( rest )?;
+                let segments: Vec<&'a str> = definition.split_whitespace().collect();
+                for segment in &segments
+                {
+                    self.segment( segment )?;
+                }
+                self.graphemes.push( ( name, segments ) );
+            }
+            "class" =>
+            {
+                let ( name, definition ) = SpecTable::definition( rest )?;
+                let members: Vec<&'a str> = definition.split_whitespace().collect();
+                for member in &members
+                {
+                    self.segment( member )?;
+                }
+                self.classes.push( ( name, members ) );
+            }
+            "stage" =>
+            {
+                self.stages.push(
+                    Stage { repeat: rest.split_whitespace().any( | w | w == "repeat" ),
+                            rules: Vec::new() } );
+            }
+            "rule" =>
+            {
+                let rule = self.rule( rest )?;
+                match self.stages.last_mut()
+                {
+                    Some( stage ) => stage.rules.push( rule ),
+                    None => { return Err( "rule outside a stage".to_string() ); }
+                }
+            }
+            _ => { return Err( format!( "unknown keyword \"{}\"", keyword ) ); }
+        }
+        Ok( () )
+    }
+
+    // The index of the tier named "boundary":
+    fn boundary_tier( &self ) -> Option<usize>
+    {
+        self.tier_names.iter().position( | name | *name == "boundary" )
+    }
+
+    // A word boundary column, if there is a boundary tier:
+    fn word_boundary( &self ) -> Option<Table<'a>>
+    {
+        let index = self.boundary_tier()?;
+        let tiers =
+            ( 0..self.tier_names.len() ).
+            map( | i | if i == index { Tier::new_slot( "word", 1 ) }
+                       else { Tier::new_gap( 1 ) } ).
+            collect();
+        Some( Table { tiers } )
+    }
+
+    // `name = definition`:
+    fn definition( s: &'a str ) -> Result<(&'a str, &'a str), String>
+    {
+        match s.find( '=' )
+        {
+            Some( i ) if ! s[..i].trim().is_empty() =>
+                Ok( ( s[..i].trim(), s[i + 1..].trim() ) ),
+            _ => Err( format!( "expected \"name = definition\": {}", s ) )
+        }
+    }
+
+    fn segment( &self, name: &str ) -> Result<&Table<'a>, String>
+    {
+        match self.segments.iter().find( | (n, _) | *n == name )
+        {
+            Some( (_, table) ) => Ok( table ),
+            None => Err( format!( "unknown segment \"{}\"", name ) )
+        }
+    }
+
+    fn class( &self, name: &str ) -> Result<&[&'a str], String>
+    {
+        match self.classes.iter().find( | (n, _) | *n == name )
+        {
+            Some( (_, members) ) => Ok( members ),
+            None => Err( format!( "unknown class \"{}\"", name ) )
+        }
+    }
+
+    fn terms( &self, s: &'a str ) -> Result<Vec<Term<'a>>, String>
+    {
+        let mut result = Vec::new();
+        for word in s.split_whitespace()
+        {
+            if word == "#"
+            {
+                result.push( Term::Edge );
+            }
+            else if word == "0"
+            {
+                continue;
+            }
+            else if word.starts_with( '{' ) && word.ends_with( '}' )
+            {
+                let name = &word[1..word.len() - 1];
+                self.class( name )?;
+                result.push( Term::Class( name ) );
+            }
+            else
+            {
+                self.segment( word )?;
+                result.push( Term::Segment( word ) );
+            }
+        }
+        Ok( result )
+    }
+
+    // `target -> replacement ( / left _ right )?`:
+    fn rule( &self, s: &'a str ) -> Result<Rule<'a>, String>
+    {
+        let arrow = s.find( "->" ).ok_or( "expected \"->\"" )?;
+        let ( change, context ) = match s.find( '/' )
+        {
+            Some( i ) if i < arrow =>
+            {
+                return Err( "the context \"/\" must follow \"->\"".to_string() );
+            }
+            Some( i ) => ( &s[..i], Some( &s[i + 1..] ) ),
+            None => ( s, None )
+        };
+        let target = self.terms( &change[..arrow] )?;
+        let replacement = self.terms( &change[arrow + 2..] )?;
+        if target.is_empty() || target.contains( &Term::Edge )
+        {
+            return Err( "the target must be a sequence of segments".to_string() );
+        }
+        for ( i, term ) in replacement.iter().enumerate()
+        {
+            if let Term::Class( name ) = term
+            {
+                match target.get( i )
+                {
+                    Some( Term::Class( source ) ) if
+                        self.class( source )?.len() == self.class( name )?.len() => (),
+                    _ => { return Err(
+                        format!( "class {{{}}} has no class of the same size \
+                                  to correspond to in the target", name ) ); }
+                }
+            }
+        }
+        let ( left, right ) = match context
+        {
+            Some( context ) =>
+            {
+                let place = context.find( '_' ).ok_or( "expected \"_\" in context" )?;
+                ( self.terms( &context[..place] )?, self.terms( &context[place + 1..] )? )
+            }
+            None => ( Vec::new(), Vec::new() )
+        };
+        Ok( Rule { target, replacement, left, right } )
+    }
+
+    /// The segments of `graphemes` after the rewrite stages.
+    pub fn segments( &self, graphemes: &str ) -> Result<Vec<&'a str>, String>
+    {
+        let mut segments = self.tokenize( graphemes )?;
+        for stage in &self.stages
+        {
+            let mut repetitions = 0;
+            loop
+            {
+                let rewritten = self.apply( stage, &segments )?;
+                let changed = rewritten != segments;
+                segments = rewritten;
+                if ! stage.repeat || ! changed
+                {
+                    break;
+                }
+                repetitions += 1;
+                if repetitions == MAX_REPETITIONS
+                {
+                    return Err( "a repeated stage does not terminate".to_string() );
+                }
+            }
+        }
+        Ok( segments )
+    }
+
+    /// The table of `graphemes`.
+    pub fn convert( &self, graphemes: &str ) -> Result<Table<'a>, String>
+    {
+        let mut result: Option<Table<'a>> = None;
+        for segment in self.segments( graphemes )?
+        {
+            let table =
+                if segment == "#"
+                {
+                    match self.word_boundary()
+                    {
+                        Some( table ) => table,
+                        None => { continue; }
+                    }
+                }
+                else { self.segment( segment )?.clone() };
+            result = Some( match result
+                           {
+                               Some( previous ) =>
+                                   Tabular::concatenate( &previous, &table ),
+                               None => table
+                           } );
+        }
+        result.ok_or_else( || "Empty segment sequence".to_string() )
+    }
+
+    // Longest-match segmentation into graphemes, replaced by their
+    // segments; whitespace gives word boundaries:
+    fn tokenize( &self, graphemes: &str ) -> Result<Vec<&'a str>, String>
+    {
+        let mut result: Vec<&'a str> = Vec::new();
+        let mut position = 0;
+        while position < graphemes.len()
+        {
+            let rest = &graphemes[position..];
+            let c = rest.chars().next().unwrap();
+            if c.is_whitespace()
+            {
+                if ! result.is_empty() && result.last() != Some( &"#" )
+                {
+                    result.push( "#" );
+                }
+                position += c.len_utf8();
+                continue;
+            }
+            let grapheme = self.graphemes.iter().
+                filter( | (g, _) | rest.starts_with( g ) ).
+                max_by_key( | (g, _) | g.len() );
+            let segment = self.segments.iter().
+                filter( | (s, _) | rest.starts_with( s ) ).
+                max_by_key( | (s, _) | s.len() );
+            match ( grapheme, segment )
+            {
+                ( Some( (g, segments) ), s )
+                    if s.is_none_or( | (s, _) | s.len() <= g.len() ) =>
+                {
+                    result.extend( segments );
+                    position += g.len();
+                }
+                ( _, Some( (s, _) ) ) =>
+                {
+                    result.push( s );
+                    position += s.len();
+                }
+                _ =>
+                {
+                    return Err( format!( "Unknown grapheme \"{}\" at position {}",
+                                         c, position ) );
+                }
+            }
+        }
+        if result.last() == Some( &"#" )
+        {
+            result.pop();
+        }
+        Ok( result )
+    }
+
+    // Whether `term` matches the segment at `position` of `segments`
+    // (an edge also matching outside the segments), with the index
+    // of the segment in the class of `term`:
+    fn matches( &self, term: &Term, segments: &[&str], position: isize )
+        -> Option<usize>
+    {
+        let segment =
+            if position < 0 || position as usize >= segments.len() { None }
+            else { Some( segments[position as usize] ) };
+        match ( term, segment )
+        {
+            ( Term::Edge, None ) | ( Term::Edge, Some( "#" ) ) => Some( 0 ),
+            ( Term::Segment( s ), Some( segment ) ) if *s == segment => Some( 0 ),
+            ( Term::Class( name ), Some( segment ) ) =>
+                self.class( name ).ok()?.iter().position( | m | *m == segment ),
+            _ => None
+        }
+    }
+
+    // The indices in their classes of the segments matched by
+    // the target of `rule` at `position`, if the rule applies there:
+    fn rule_matches( &self, rule: &Rule, segments: &[&str], position: usize )
+        -> Option<Vec<usize>>
+    {
+        let start = position as isize;
+        let mut indices = Vec::new();
+        for ( i, term ) in rule.target.iter().enumerate()
+        {
+            indices.push( self.matches( term, segments, start + i as isize )? );
+        }
+        for ( i, term ) in rule.left.iter().rev().enumerate()
+        {
+            self.matches( term, segments, start - 1 - i as isize )?;
+        }
+        let end = start + rule.target.len() as isize;
+        for ( i, term ) in rule.right.iter().enumerate()
+        {
+            self.matches( term, segments, end + i as isize )?;
+        }
+        Some( indices )
+    }
+
+    fn apply( &self, stage: &Stage<'a>, segments: &[&'a str] )
+        -> Result<Vec<&'a str>, String>
+    {
+        let mut result = Vec::new();
+        let mut position = 0;
+        'segments: while position < segments.len()
+        {
+            for rule in &stage.rules
+            {
+                if let Some( indices ) = self.rule_matches( rule, segments, position )
+                {
+                    for ( i, term ) in rule.replacement.iter().enumerate()
+                    {
+                        match term
+                        {
+                            Term::Segment( s ) => result.push( *s ),
+                            Term::Class( name ) =>
+                                result.push( self.class( name )?[indices[i]] ),
+                            Term::Edge => ()
+                        }
+                    }
+                    position += rule.target.len();
+                    continue 'segments;
+                }
+            }
+            result.push( segments[position] );
+            position += 1;
+        }
+        Ok( result )
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::SpecTable;
+    use crate::table::Table;
+
+    const SPEC: &str = "
+        tiers tongue voice airflow
+        segment a = back, voiced, open
+        segment e = front, voiced, open
+        segment b = _, voiced, stop
+        segment d = dentialveolar, voiced, stop
+        segment p = _, voiceless, stop
+        segment t = dentialveolar, voiceless, stop
+        segment s = dentialveolar, voiceless, slit
+        segment c = 2 dentialveolar, 2 voiceless, stop slit
+        segment h = _, voiceless, approximate
+        grapheme sz = s
+        grapheme tt = t t
+        class vowel = a e
+        class voiced = b d
+        class voiceless = p t
+        stage voice repeat
+        rule {voiced} -> {voiceless} / _ {voiceless}
+        stage affricates
+        rule t s -> c
+        rule h -> 0 / {vowel} _ #  // final h is silent
+    ";
+
+    fn segments( spec: &SpecTable, s: &str ) -> String
+    {
+        spec.segments( s ).unwrap().join( " " )
+    }
+
+    #[test]
+    fn rules_rewrite_the_segments()
+    {
+        let spec = SpecTable::from_spec( SPEC ).unwrap();
+        assert_eq!( segments( &spec, "bdt" ), "p t t" );
+        assert_eq!( segments( &spec, "atsz" ), "a c" );
+        assert_eq!( segments( &spec, "ah" ), "a" );
+        assert_eq!( segments( &spec, "aha" ), "a h a" );
+        assert_eq!( segments( &spec, "ah beh" ), "a # b e" );
+        assert_eq!( segments( &spec, "atta" ), "a t t a" );
+        assert_eq!( spec.convert( "tsz" ).unwrap(), spec.convert( "c" ).unwrap() );
+    }
+
+    #[test]
+    fn errors_are_reported()
+    {
+        assert_eq!( SpecTable::from_spec( "tiers voice\nsegment a = voiced, open" ).
+                    err().unwrap(),
+                    "line 2: segment a has 2 tiers instead of 1" );
+        assert_eq!( SpecTable::from_spec( "tiers voice\nrule a -> b" ).err().unwrap(),
+                    "line 2: unknown segment \"a\"" );
+        // malformed rules are errors, not panics:
+        for ( rule, error ) in [
+            ( "rule a / b -> a", "line 3: the context \"/\" must follow \"->\"" ),
+            ( "rule a / _ b", "line 3: expected \"->\"" ),
+            ( "rule -> a", "line 3: the target must be a sequence of segments" ),
+            ( "rule a -> a / a", "line 3: expected \"_\" in context" ) ]
+        {
+            let spec = format!( "tiers voice\nsegment a = voiced\n{}", rule );
+            assert_eq!( SpecTable::from_spec( &spec ).err().unwrap(), error );
+        }
+        let spec = SpecTable::from_spec( SPEC ).unwrap();
+        assert_eq!( spec.segments( "ax" ).unwrap_err(),
+                    "Unknown grapheme \"x\" at position 1" );
+    }
+
+    #[test]
+    fn word_boundaries_are_on_the_boundary_tier()
+    {
+        let spec = SpecTable::from_spec(
+            "tiers voice airflow boundary
+             segment a = voiced, open
+             segment p = voiceless, stop" ).unwrap();
+        assert_eq!( spec.convert( "pa pa" ).unwrap(),
+                    Table::parse( "voiceless voiced _ voiceless voiced, \
+                                   stop open _ stop open, \
+                                   _ _ word _ _;" ).unwrap() );
+        // without a boundary tier, words are simply concatenated:
+        let spec = SpecTable::from_spec( SPEC ).unwrap();
+        assert_eq!( spec.convert( "a a" ).unwrap(), spec.convert( "aa" ).unwrap() );
+    }
+}
@@ -19,7 +19,7 @@ use crate::table_grammar;
 // and each slot contains information on how many boundaries
 // it spans on other tiers.
 
-impl<'a> Label for &'a str
+impl Label for &str
 {
 }
 
@@ -57,7 +57,7 @@ impl<'a> Slot<'a>
 {
     pub fn new( element: Element<'a> ) -> Self
     {
-        Slot { element: element,
+        Slot { element,
                left_aligned: false,
                right_aligned: false, }
     }
@@ -71,6 +71,16 @@ impl<'a> Slot<'a>
         }
     }
 
+    /// The element of the slot, `None` for a gap.
+    pub fn element( &self ) -> Option<&'a str>
+    {
+        match self.element
+        {
+            Element::Element( el ) => Some( el ),
+            _ => None
+        }
+    }
+
     pub fn set_element( &mut self, el: &'a str )
     {
         self.element = Element::Element( el );
@@ -132,6 +142,32 @@ impl<'a> IndexMut<usize> for Tier<'a>
 
 impl<'a> Tier<'a>
 {
+    /// A tier consisting of a single gap spanning `width` columns.
+    pub fn new_gap( width: usize ) -> Self
+    {
+        let mut slots = Vec::new();
+        for i in 0..width
+        {
+            let mut slot = Slot::new( Element::Null );
+            slot.set_left_aligned( i == 0 );
+            slot.set_right_aligned( i == width - 1 );
+            slots.push( slot );
+        }
+        Tier { slots }
+    }
+
+    /// A tier consisting of a single slot of `element`
+    /// spanning `width` columns.
+    pub fn new_slot( element: &'a str, width: usize ) -> Self
+    {
+        let mut tier = Tier::new_gap( width );
+        for slot in tier.slots.iter_mut()
+        {
+            slot.set_element( element );
+        }
+        tier
+    }
+
     fn concatenate( &self, other: &Self ) -> Self
     {
         let mut slots = Vec::clone( &self.slots );
@@ -142,7 +178,7 @@ impl<'a> Tier<'a>
     }
 }
 
-#[derive( PartialEq, Eq, std::hash::Hash, Clone, PartialOrd, Ord )]
+#[derive( PartialEq, Eq, std::hash::Hash, Clone, PartialOrd, Ord, Debug )]
 pub struct Table<'a>
 {
     // temporarily public:
@@ -151,11 +187,118 @@ pub struct Table<'a>
 
 impl<'a> Table<'a>
 {
+    pub fn number_of_columns( &self ) -> usize
+    {
+        match self.tiers.first()
+        {
+            Some( tier ) => Vec::len( &tier.slots ),
+            None => 0
+        }
+    }
+
+    /// Parse a table (`from_str` for input not living forever).
+    pub fn parse( input: &'a str ) -> Result<Self, String>
+    {
+        table_grammar::table_grammar::table( input ).
+            map_err( | e | e.to_string() )
+    }
+
+    /// The table made of the columns `start..end`.
+    pub fn columns( &self, start: usize, end: usize ) -> Self
+    {
+        let mut tiers = Vec::new();
+        for tier in &self.tiers
+        {
+            tiers.push( Tier { slots: tier.slots[start..end].to_vec() } );
+        }
+        Table { tiers }
+    }
+
+    /// Whether this table is an abstraction of `other`: its columns
+    /// are unions of columns of `other`, and each of its cells is
+    /// either a cell of `other` or a gap over whole cells of `other`.
+    pub fn subsumes( &self, other: &Self ) -> bool
+    {
+        let ( m, n ) = ( self.number_of_columns(), other.number_of_columns() );
+        if self.tiers.len() != other.tiers.len() || m > n
+        {
+            return false;
+        }
+        if m == 0
+        {
+            return n == 0;
+        }
+        // the columns of `other` where the columns of this table
+        // start, tried in lexicographic order:
+        let mut starts: Vec<usize> = ( 0..m ).collect();
+        loop
+        {
+            if self.subsumes_with( other, &starts )
+            {
+                return true;
+            }
+            let mut i = m - 1;
+            while i > 0 && starts[i] == n - m + i
+            {
+                i -= 1;
+            }
+            if i == 0
+            {
+                return false;
+            }
+            starts[i] += 1;
+            for j in i + 1..m
+            {
+                starts[j] = starts[j - 1] + 1;
+            }
+        }
+    }
+
+    // Whether this table subsumes `other`, its columns starting
+    // at the columns `starts` of `other`:
+    fn subsumes_with( &self, other: &Self, starts: &[usize] ) -> bool
+    {
+        let n = other.number_of_columns();
+        let end = | column: usize |
+            if column + 1 < starts.len() { starts[column + 1] } else { n };
+        for ( tier, other_tier ) in self.tiers.iter().zip( &other.tiers )
+        {
+            // whether a cell of `other` starts at a column:
+            let boundary = | column: usize |
+                column == 0 || column == n || other_tier[column - 1].is_right_aligned();
+            let mut start = 0;
+            while start < tier.slots.len()
+            {
+                let mut last = start;
+                while ! tier[last].is_right_aligned() && last + 1 < tier.slots.len()
+                {
+                    last += 1;
+                }
+                let ( a, b ) = ( starts[start], end( last ) );
+                if ! boundary( a ) || ! boundary( b )
+                {
+                    return false;
+                }
+                // an element stands for a single cell with that element:
+                if let Some( element ) = tier[start].element()
+                {
+                    if ( a + 1..b ).any( boundary ) ||
+                        other_tier[a].element() != Some( element )
+                    {
+                        return false;
+                    }
+                }
+                start = last + 1;
+            }
+        }
+        true
+    }
+
     pub fn same_column( &self, col1: usize, col2: usize ) -> bool
     {
         for i in 0..Vec::len( &self.tiers )
         {
-            if &self.tiers[i].slots[col1] != &self.tiers[i].slots[col2]
+            if self.tiers[i].slots[col1] != self.tiers[i].slots[col2]
             {
                 return false;
             }
@@ -216,16 +359,16 @@ impl<'a> Table<'a>
         let tier = &self.tiers[tier_index];
         // let slot = &tier.slots[slot_index];
         let slot = &tier[slot_index];
-        if Slot::is_gap( &slot )
+        if Slot::is_gap( slot )
         {
             // is there a slot to its right?
-            if Slot::is_right_aligned( &slot ) &&
+            if Slot::is_right_aligned( slot ) &&
                 ( slot_index < Vec::len( &tier.slots ) - 1 ) &&
                 Slot::is_gap( &tier[slot_index + 1] )
             {
                 Some(
                     Self::merge_cell_with_next(
-                        &self, tier_index, slot_index ) )
+                        self, tier_index, slot_index ) )
             }
             else { None }
         }
@@ -234,7 +377,7 @@ impl<'a> Table<'a>
             // All you have to do is converting it into a gap.
             Some(
                 Self::null_element(
-                    &self, tier_index, slot_index ) )
+                    self, tier_index, slot_index ) )
         }
     }
 
@@ -246,13 +389,9 @@ impl<'a> Table<'a>
             let tier = &self.tiers[i];
             for j in 0..Vec::len( &tier.slots )
             {
-                match Self::abstract_element( &self, i, j )
+                if let Some( new_tab ) = Self::abstract_element( self, i, j )
                 {
-                    Some( new_tab ) =>
-                    {
-                        Set::insert( &mut result, new_tab );
-                    }
-                    _ => ()
+                    Set::insert( &mut result, new_tab );
                 }
             }
         }
@@ -296,8 +435,8 @@ impl<'a> tabular::Tabular for Table<'a>
     fn abstractions( &self ) -> Set<Self>
     {
         let mut result: Set<Self> = Set::new();
-        Set::insert( &mut result, Self::clone( &self ) );
-        let mut todo = vec![Self::clone( &self )];
+        Set::insert( &mut result, Self::clone( self ) );
+        let mut todo = vec![Self::clone( self )];
         let mut index = 0;
         loop
         {
@@ -306,16 +445,12 @@ impl<'a> tabular::Tabular for Table<'a>
                 IntoIterator::into_iter(
                     Self::immediate_abstractions( &todo[index] ) )
             {
-                match <[_]>::binary_search( &todo, &abstr )
+                if <[_]>::binary_search( &todo, &abstr ).is_err()
                 {
-                    Err( _ ) =>
-                    {
-                        Vec::push(
-                            &mut todo,
-                            Self::clone( &abstr ) );
-                        Set::insert( &mut result, abstr );
-                    }
-                    _ => ()
+                    Vec::push(
+                        &mut todo,
+                        Self::clone( &abstr ) );
+                    Set::insert( &mut result, abstr );
                 }
             }
             index += 1;
@@ -347,7 +482,7 @@ impl<'a> fmt::Display for Table<'a>
             let mut colspan = 1;
             for slot in <[_]>::iter( &tier.slots )
             {
-                if Slot::is_right_aligned( &slot )
+                if Slot::is_right_aligned( slot )
                 {
                     write!( s, "
     <td colspan={}>{}</td>", &colspan, &slot.element ).ok();
@@ -365,3 +500,27 @@ impl<'a> fmt::Display for Table<'a>
 </table>\n" )
     }
 }
+
+#[cfg(test)]
+mod tests
+{
+    use super::Table;
+    use crate::tabular::Tabular;
+
+    #[test]
+    fn tables_are_subsumed_by_their_abstractions()
+    {
+        let long = Table::from_str( "2 voiced, 2 open, X X;" ).unwrap();
+        let voiced = Table::from_str( "voiced, _, _;" ).unwrap();
+        let timed = Table::from_str( "2 voiced, 2 _, X X;" ).unwrap();
+        assert!( voiced.subsumes( &long ) );
+        assert!( timed.subsumes( &long ) );
+        assert!( long.subsumes( &long ) );
+        assert!( ! long.subsumes( &voiced ) );
+        assert!( ! Table::from_str( "voiceless, _, _;" ).unwrap().subsumes( &long ) );
+        // an element of a contour is not an abstraction of it:
+        let contour = Table::from_str( "2 voiceless, stop slit;" ).unwrap();
+        assert!( ! Table::from_str( "_, stop;" ).unwrap().subsumes( &contour ) );
+        assert!( Table::from_str( "voiceless, _;" ).unwrap().subsumes( &contour ) );
+    }
+}